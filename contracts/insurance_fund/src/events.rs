@@ -0,0 +1,38 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, Symbol, Val};
+
+use crate::FundAction;
+
+/// Schema version in every event topic, bumped when a payload changes shape
+pub const EVENT_VERSION: u32 = 1;
+
+const CONTRACT: &str = "insurance_fund";
+
+/// Publish `data` under `(contract, event name, EVENT_VERSION, primary id)`
+pub(crate) fn publish<I, D>(env: &Env, name: &str, id: I, data: D)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    env.events().publish(
+        (Symbol::new(env, CONTRACT), Symbol::new(env, name), EVENT_VERSION, id),
+        data,
+    );
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundDepositEvent {
+    pub from: Address,
+    pub action: FundAction,
+    pub amount: i128,
+    pub balance: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShortfallCoveredEvent {
+    pub position_id: BytesN<32>,
+    pub requested: i128,
+    pub covered: i128,
+    pub balance: i128,
+}
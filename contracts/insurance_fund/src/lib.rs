@@ -0,0 +1,176 @@
+#![no_std]
+use soroban_sdk::{contract, contractimpl, contracttype, token, Address, BytesN, Env, Vec};
+
+mod events;
+
+#[cfg(test)]
+mod test;
+
+pub use events::*;
+
+// Stablecoin reserve fed by the insurance share of liquidation penalties.
+// The liquidation manager draws on it to cover debt left on a position once
+// all of its collateral has been sold.
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FundAction {
+    Deposit,
+    Penalty(BytesN<32>),
+    Payout(BytesN<32>),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct FundEntry {
+    pub action: FundAction,
+    pub amount: i128,
+    pub counterparty: Address,
+    pub balance: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Stablecoin,
+    LiquidationManager,
+    Balance,
+    EntryCount,
+    Entry(u32),
+}
+
+#[contract]
+pub struct InsuranceFund;
+
+#[contractimpl]
+impl InsuranceFund {
+    pub fn initialize(env: Env, admin: Address, stablecoin: Address, liquidation_manager: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Stablecoin, &stablecoin);
+        env.storage().instance().set(&DataKey::LiquidationManager, &liquidation_manager);
+    }
+
+    fn require_admin(env: &Env) {
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("admin not set");
+        admin.require_auth();
+    }
+
+    fn require_liquidation_manager(env: &Env) {
+        let liquidation_manager: Address = env.storage()
+            .instance()
+            .get(&DataKey::LiquidationManager)
+            .expect("liquidation manager not set");
+        liquidation_manager.require_auth();
+    }
+
+    pub fn set_liquidation_manager(env: Env, liquidation_manager: Address) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::LiquidationManager, &liquidation_manager);
+    }
+
+    /// Top up the fund with `amount` of stablecoin from `from`
+    pub fn deposit(env: Env, from: Address, amount: i128) {
+        Self::receive(&env, from, amount, FundAction::Deposit);
+    }
+
+    /// Deposit the insurance share of the penalty charged on a liquidation of
+    /// `position_id` (liquidation manager only)
+    pub fn deposit_penalty(env: Env, from: Address, amount: i128, position_id: BytesN<32>) {
+        Self::require_liquidation_manager(&env);
+        Self::receive(&env, from, amount, FundAction::Penalty(position_id));
+    }
+
+    /// Pay up to `amount` of a position's shortfall to `recipient` (liquidation
+    /// manager only). Returns the amount actually covered, which is capped at
+    /// the fund balance.
+    pub fn cover_shortfall(env: Env, position_id: BytesN<32>, amount: i128, recipient: Address) -> i128 {
+        Self::require_liquidation_manager(&env);
+
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+
+        let mut balance = Self::get_balance(env.clone());
+        let covered = amount.min(balance);
+        if covered > 0 {
+            token::Client::new(&env, &Self::stablecoin(&env)).transfer(&env.current_contract_address(), &recipient, &covered);
+            balance -= covered;
+            env.storage().instance().set(&DataKey::Balance, &balance);
+        }
+
+        let index = Self::record(&env, FundAction::Payout(position_id.clone()), covered, &recipient, balance);
+        events::publish(
+            &env,
+            "ShortfallCovered",
+            index,
+            ShortfallCoveredEvent { position_id, requested: amount, covered, balance },
+        );
+        covered
+    }
+
+    /// Stablecoin held by the fund (view function)
+    pub fn get_balance(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::Balance).unwrap_or(0i128)
+    }
+
+    /// Get up to `limit` fund entries, oldest first, starting at `start`
+    pub fn get_history(env: Env, start: u32, limit: u32) -> Vec<FundEntry> {
+        let count = Self::get_history_len(env.clone());
+        let end = start.saturating_add(limit).min(count);
+
+        let mut entries = Vec::new(&env);
+        for index in start..end {
+            if let Some(entry) = env.storage().persistent().get(&DataKey::Entry(index)) {
+                entries.push_back(entry);
+            }
+        }
+        entries
+    }
+
+    pub fn get_history_len(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::EntryCount).unwrap_or(0u32)
+    }
+
+    fn receive(env: &Env, from: Address, amount: i128, action: FundAction) {
+        from.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+
+        token::Client::new(env, &Self::stablecoin(env)).transfer(&from, &env.current_contract_address(), &amount);
+
+        let balance = Self::get_balance(env.clone()) + amount;
+        env.storage().instance().set(&DataKey::Balance, &balance);
+
+        let index = Self::record(env, action.clone(), amount, &from, balance);
+        events::publish(
+            env,
+            "FundDeposit",
+            index,
+            FundDepositEvent { from, action, amount, balance },
+        );
+    }
+
+    fn record(env: &Env, action: FundAction, amount: i128, counterparty: &Address, balance: i128) -> u32 {
+        let index = Self::get_history_len(env.clone());
+        let entry = FundEntry {
+            action,
+            amount,
+            counterparty: counterparty.clone(),
+            balance,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&DataKey::Entry(index), &entry);
+        env.storage().instance().set(&DataKey::EntryCount, &(index + 1));
+        index
+    }
+
+    fn stablecoin(env: &Env) -> Address {
+        env.storage().instance().get(&DataKey::Stablecoin).expect("stablecoin not set")
+    }
+}
@@ -0,0 +1,125 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Events, MockAuth, MockAuthInvoke};
+use soroban_sdk::token::StellarAssetClient;
+use soroban_sdk::{vec, IntoVal, Symbol, Val};
+
+struct Setup {
+    env: Env,
+    fund: InsuranceFundClient<'static>,
+    manager: Address,
+    depositor: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let stablecoin = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let manager = Address::generate(&env);
+    let fund = InsuranceFundClient::new(&env, &env.register(InsuranceFund, ()));
+    fund.initialize(&admin, &stablecoin, &manager);
+
+    let depositor = Address::generate(&env);
+    StellarAssetClient::new(&env, &stablecoin).mint(&depositor, &10_000);
+    Setup { env, fund, manager, depositor }
+}
+
+/// Events the fund itself published in the last call, leaving out the
+/// stablecoin's transfer events
+fn fund_events(s: &Setup) -> soroban_sdk::Vec<(Address, soroban_sdk::Vec<Val>, Val)> {
+    let mut events = soroban_sdk::Vec::new(&s.env);
+    for event in s.env.events().all().iter() {
+        if event.0 == s.fund.address {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
+fn event<D: IntoVal<Env, Val>>(s: &Setup, name: &str, index: u32, data: D) -> (Address, soroban_sdk::Vec<Val>, Val) {
+    (
+        s.fund.address.clone(),
+        (Symbol::new(&s.env, "insurance_fund"), Symbol::new(&s.env, name), EVENT_VERSION, index).into_val(&s.env),
+        data.into_val(&s.env),
+    )
+}
+
+#[test]
+fn deposits_emit_fund_deposit() {
+    let s = setup();
+    let position_id = BytesN::from_array(&s.env, &[1u8; 32]);
+
+    s.fund.deposit(&s.depositor, &4_000);
+    assert_eq!(
+        fund_events(&s),
+        vec![
+            &s.env,
+            event(&s, "FundDeposit", 0, FundDepositEvent {
+                from: s.depositor.clone(),
+                action: FundAction::Deposit,
+                amount: 4_000,
+                balance: 4_000,
+            })
+        ]
+    );
+
+    s.fund.deposit_penalty(&s.depositor, &1_000, &position_id);
+    assert_eq!(
+        fund_events(&s),
+        vec![
+            &s.env,
+            event(&s, "FundDeposit", 1, FundDepositEvent {
+                from: s.depositor.clone(),
+                action: FundAction::Penalty(position_id),
+                amount: 1_000,
+                balance: 5_000,
+            })
+        ]
+    );
+}
+
+#[test]
+fn cover_shortfall_emits_shortfall_covered() {
+    let s = setup();
+    let position_id = BytesN::from_array(&s.env, &[1u8; 32]);
+    s.fund.deposit(&s.depositor, &4_000);
+
+    // Capped at the balance
+    let covered = s.fund.cover_shortfall(&position_id, &6_000, &s.manager);
+    assert_eq!(covered, 4_000);
+    assert_eq!(
+        fund_events(&s),
+        vec![
+            &s.env,
+            event(&s, "ShortfallCovered", 1, ShortfallCoveredEvent {
+                position_id,
+                requested: 6_000,
+                covered: 4_000,
+                balance: 0,
+            })
+        ]
+    );
+}
+
+#[test]
+fn deposit_penalty_requires_the_liquidation_manager() {
+    let s = setup();
+    let position_id = BytesN::from_array(&s.env, &[1u8; 32]);
+
+    // The depositor's own authorization is not enough to book a penalty
+    s.env.mock_auths(&[MockAuth {
+        address: &s.depositor,
+        invoke: &MockAuthInvoke {
+            contract: &s.fund.address,
+            fn_name: "deposit_penalty",
+            args: (s.depositor.clone(), 1_000i128, position_id.clone()).into_val(&s.env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(s.fund.try_deposit_penalty(&s.depositor, &1_000, &position_id).is_err());
+    assert_eq!(s.fund.get_balance(), 0);
+}
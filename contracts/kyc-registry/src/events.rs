@@ -0,0 +1,35 @@
+use soroban_sdk::{contracttype, Address, Env, IntoVal, String, Symbol, Val};
+
+use crate::KYCStatus;
+
+/// Schema version in every event topic, bumped when a payload changes shape
+pub const EVENT_VERSION: u32 = 1;
+
+const CONTRACT: &str = "kyc_registry_contract";
+
+/// Publish `data` under `(contract, event name, EVENT_VERSION, primary id)`
+pub(crate) fn publish<I, D>(env: &Env, name: &str, id: I, data: D)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    env.events().publish(
+        (Symbol::new(env, CONTRACT), Symbol::new(env, name), EVENT_VERSION, id),
+        data,
+    );
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderAddedEvent {
+    pub added_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KycUpdatedEvent {
+    pub provider: Address,
+    pub status: KYCStatus,
+    pub provider_name: String,
+    pub verified_at: u64,
+}
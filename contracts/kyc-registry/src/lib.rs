@@ -1,5 +1,12 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, String};
+
+mod events;
+
+#[cfg(test)]
+mod test;
+
+pub use events::*;
 
 // KYC Registry for Legasi protocol
 // Tracks user verification status
@@ -13,7 +20,7 @@ pub enum KYCStatus {
 }
 
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UserKYC {
     pub user: Address,
     pub status: KYCStatus,
@@ -29,17 +36,27 @@ pub enum DataKey {
     KYCProvider(Address),
 }
 
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAuthorized = 3,
+    KycNotFound = 4,
+}
+
 #[contract]
 pub struct KYCRegistryContract;
 
 #[contractimpl]
 impl KYCRegistryContract {
     /// Initialize the contract
-    pub fn initialize(env: Env, admin: Address) -> Result<(), String> {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
         admin.require_auth();
 
         if env.storage().instance().has(&DataKey::Admin) {
-            return Err(String::from_str(&env, "Already initialized"));
+            return Err(Error::AlreadyInitialized);
         }
 
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -52,22 +69,29 @@ impl KYCRegistryContract {
         env: Env,
         admin: Address,
         provider: Address,
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         admin.require_auth();
 
         let stored_admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .ok_or(String::from_str(&env, "Not initialized"))?;
+            .ok_or(Error::NotInitialized)?;
 
         if admin != stored_admin {
-            return Err(String::from_str(&env, "Not authorized"));
+            return Err(Error::NotAuthorized);
         }
 
         env.storage()
             .instance()
-            .set(&DataKey::KYCProvider(provider), &true);
+            .set(&DataKey::KYCProvider(provider.clone()), &true);
+
+        events::publish(
+            &env,
+            "ProviderAdded",
+            provider,
+            ProviderAddedEvent { added_by: admin },
+        );
 
         Ok(())
     }
@@ -79,24 +103,40 @@ impl KYCRegistryContract {
         user: Address,
         status: KYCStatus,
         provider_name: String,
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         provider.require_auth();
 
         // Verify provider is authorized
-        if !env.storage().instance().has(&DataKey::KYCProvider(provider)) {
-            return Err(String::from_str(&env, "Not authorized"));
+        if !env
+            .storage()
+            .instance()
+            .has(&DataKey::KYCProvider(provider.clone()))
+        {
+            return Err(Error::NotAuthorized);
         }
 
         let user_kyc = UserKYC {
             user: user.clone(),
-            status,
-            provider: provider_name,
+            status: status.clone(),
+            provider: provider_name.clone(),
             verified_at: env.ledger().timestamp(),
         };
 
         env.storage()
             .persistent()
-            .set(&DataKey::UserKYC(user), &user_kyc);
+            .set(&DataKey::UserKYC(user.clone()), &user_kyc);
+
+        events::publish(
+            &env,
+            "KycUpdated",
+            user,
+            KycUpdatedEvent {
+                provider,
+                status,
+                provider_name,
+                verified_at: user_kyc.verified_at,
+            },
+        );
 
         Ok(())
     }
@@ -112,10 +152,10 @@ impl KYCRegistryContract {
     }
 
     /// Get user KYC data
-    pub fn get_kyc(env: Env, user: Address) -> Result<UserKYC, String> {
+    pub fn get_kyc(env: Env, user: Address) -> Result<UserKYC, Error> {
         env.storage()
             .persistent()
             .get(&DataKey::UserKYC(user))
-            .ok_or(String::from_str(&env, "KYC not found"))
+            .ok_or(Error::KycNotFound)
     }
 }
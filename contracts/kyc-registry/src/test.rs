@@ -0,0 +1,102 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{vec, IntoVal, Symbol, Val};
+
+struct Setup {
+    env: Env,
+    registry: KYCRegistryContractClient<'static>,
+    admin: Address,
+    provider: Address,
+}
+
+/// Registry with one authorized provider
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let registry = KYCRegistryContractClient::new(&env, &env.register_contract(None, KYCRegistryContract));
+    let admin = Address::generate(&env);
+    registry.initialize(&admin);
+    let provider = Address::generate(&env);
+    registry.add_provider(&admin, &provider);
+
+    Setup { env, registry, admin, provider }
+}
+
+/// Events published since the log held `since` entries; the test
+/// environment keeps every event from earlier calls
+fn events_since(s: &Setup, since: u32) -> soroban_sdk::Vec<(Address, soroban_sdk::Vec<Val>, Val)> {
+    let all = s.env.events().all();
+    all.slice(since..all.len())
+}
+
+/// The event `name` as the registry publishes it
+fn event<I, D>(s: &Setup, name: &str, id: I, data: D) -> (Address, soroban_sdk::Vec<Val>, Val)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    (
+        s.registry.address.clone(),
+        (Symbol::new(&s.env, "kyc_registry_contract"), Symbol::new(&s.env, name), EVENT_VERSION, id)
+            .into_val(&s.env),
+        data.into_val(&s.env),
+    )
+}
+
+#[test]
+fn add_provider_emits_provider_added() {
+    let s = setup();
+    let provider = Address::generate(&s.env);
+    let since = s.env.events().all().len();
+    s.registry.add_provider(&s.admin, &provider);
+
+    assert_eq!(
+        events_since(&s, since),
+        vec![
+            &s.env,
+            event(&s, "ProviderAdded", provider, ProviderAddedEvent { added_by: s.admin.clone() })
+        ]
+    );
+}
+
+#[test]
+fn update_kyc_emits_kyc_updated() {
+    let s = setup();
+    let user = Address::generate(&s.env);
+    let provider_name = String::from_str(&s.env, "sep12");
+    let since = s.env.events().all().len();
+    s.registry.update_kyc(&s.provider, &user, &KYCStatus::Approved, &provider_name);
+
+    assert_eq!(
+        events_since(&s, since),
+        vec![
+            &s.env,
+            event(&s, "KycUpdated", user.clone(), KycUpdatedEvent {
+                provider: s.provider.clone(),
+                status: KYCStatus::Approved,
+                provider_name,
+                verified_at: 1_000,
+            })
+        ]
+    );
+    assert!(s.registry.is_verified(&user));
+}
+
+#[test]
+fn only_authorized_callers_change_the_registry() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    let user = Address::generate(&s.env);
+
+    assert_eq!(s.registry.try_add_provider(&stranger, &stranger), Err(Ok(Error::NotAuthorized)));
+    assert_eq!(
+        s.registry.try_update_kyc(&stranger, &user, &KYCStatus::Approved, &String::from_str(&s.env, "sep12")),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(s.registry.try_initialize(&stranger), Err(Ok(Error::AlreadyInitialized)));
+    assert_eq!(s.registry.try_get_kyc(&user), Err(Ok(Error::KycNotFound)));
+}
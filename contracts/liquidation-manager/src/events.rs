@@ -0,0 +1,46 @@
+use soroban_sdk::{contracttype, BytesN, Env, IntoVal, Symbol, Val};
+
+/// Schema version in every event topic, bumped when a payload changes shape
+pub const EVENT_VERSION: u32 = 1;
+
+const CONTRACT: &str = "liquidation_manager_contract";
+
+/// Publish `data` under `(contract, event name, EVENT_VERSION, primary id)`
+pub(crate) fn publish<I, D>(env: &Env, name: &str, id: I, data: D)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    env.events().publish(
+        (Symbol::new(env, CONTRACT), Symbol::new(env, name), EVENT_VERSION, id),
+        data,
+    );
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationIntentEvent {
+    pub position_id: BytesN<32>,
+    pub collateral_to_sell: i128,
+    pub min_out: i128,
+    pub deadline: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptAcceptedEvent {
+    pub position_id: BytesN<32>,
+    pub proceeds: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntentExpiredEvent {
+    pub position_id: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntentCancelledEvent {
+    pub position_id: BytesN<32>,
+}
@@ -0,0 +1,65 @@
+use soroban_sdk::{contractclient, contracttype, Address, BytesN, Env, String, Symbol};
+
+// Client bindings for the contracts this manager reads from. The types mirror
+// the callee's #[contracttype] definitions field for field so values decode
+// as-is.
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PositionStatus {
+    Open,
+    InLiquidationCooldown,
+    Closable,
+    Closed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Position {
+    pub owner: Address,
+    pub principal: i128,
+    pub accrued_interest: i128,
+    pub collateral_ref: BytesN<32>,
+    pub asset: Symbol,
+    pub ltv_bps: i128,
+    pub status: PositionStatus,
+    pub last_oracle_round: i128,
+    pub nonce: i128,
+    pub created_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Attestation {
+    pub custodian: BytesN<32>,
+    pub amount: i128,
+    pub vault_ref: BytesN<32>,
+    pub timestamp: u64,
+}
+
+#[contractclient(name = "LoansClient")]
+pub trait LoansInterface {
+    fn get_position(env: Env, position_id: BytesN<32>) -> Option<Position>;
+
+    fn get_attestation(env: Env, position_id: BytesN<32>) -> Option<Attestation>;
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PriceData {
+    pub asset_pair: String,
+    pub price: i128,
+    pub confidence: u32,
+    pub timestamp: u64,
+    pub source: String,
+}
+
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracleInterface {
+    fn get_price(env: Env, asset_pair: String) -> PriceData;
+}
+
+#[contractclient(name = "PolicyRegistryClient")]
+pub trait PolicyRegistryInterface {
+    fn get_parameter(env: Env, name: String) -> i128;
+}
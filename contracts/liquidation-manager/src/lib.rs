@@ -1,5 +1,16 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, Address, Bytes, BytesN, Env, String, Symbol, Vec,
+};
+
+mod events;
+pub mod interfaces;
+
+#[cfg(test)]
+mod test;
+
+pub use events::*;
+use interfaces::{LoansClient, PolicyRegistryClient, PriceOracleClient};
 
 // Liquidation Manager for Legasi protocol
 // Monitors positions and triggers soft liquidations
@@ -7,8 +18,10 @@ use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Ve
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LiquidationIntent {
-    pub id: String,
-    pub position_id: String,
+    pub id: BytesN<32>,
+    pub number: u64,
+    pub position_id: BytesN<32>,
+    pub nonce: u64,
     pub collateral_to_sell: i128,
     pub min_output: i128,
     pub deadline: u64,
@@ -27,14 +40,45 @@ pub enum IntentStatus {
 #[contracttype]
 #[derive(Clone, Debug)]
 pub enum DataKey {
-    Intent(String),
-    PositionIntents(String),
+    Intent(BytesN<32>),
+    IntentNumber(u64),
+    PositionIntents(BytesN<32>),
     NextIntentId,
     LoansContract,
     PriceOracle,
     PolicyRegistry,
+    Admin,
+    Operator,
+    AssetPair(Symbol),
 }
 
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    PositionNotFound = 3,
+    PositionHealthy = 4,
+    PositionHasActiveIntent = 5,
+    DuplicateNonce = 6,
+    IntentNotFound = 7,
+    IntentNotPending = 8,
+    IntentExpired = 9,
+    IntentNotExpired = 10,
+    OutputBelowMinimum = 11,
+    AssetPairNotSet = 12,
+    AssetPairTooLong = 13,
+    ThresholdNotSet = 14,
+    PriceStale = 15,
+    InvalidPrice = 16,
+}
+
+/// Oracle prices carry 8 decimal places
+const PRICE_SCALE: i128 = 100_000_000;
+
+const BPS: i128 = 10_000;
+
 #[contract]
 pub struct LiquidationManagerContract;
 
@@ -43,14 +87,18 @@ impl LiquidationManagerContract {
     /// Initialize the contract
     pub fn initialize(
         env: Env,
+        admin: Address,
+        operator: Address,
         loans_contract: Address,
         price_oracle: Address,
         policy_registry: Address,
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         if env.storage().instance().has(&DataKey::LoansContract) {
-            return Err(String::from_str(&env, "Already initialized"));
+            return Err(Error::AlreadyInitialized);
         }
 
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Operator, &operator);
         env.storage()
             .instance()
             .set(&DataKey::LoansContract, &loans_contract);
@@ -65,48 +113,83 @@ impl LiquidationManagerContract {
         Ok(())
     }
 
-    /// Check if a position needs liquidation
-    pub fn check_liquidation(env: Env, position_id: String) -> Result<bool, String> {
-        // Get position from Loans contract
-        let loans_contract: Address = env
-            .storage()
+    /// Replace the operator allowed to create and execute intents
+    pub fn set_operator(env: Env, operator: Address) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        env.storage().instance().set(&DataKey::Operator, &operator);
+        Ok(())
+    }
+
+    pub fn get_operator(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Operator)
+    }
+
+    /// Map a Loans asset symbol to its oracle pair, e.g. `BTC` -> "BTC/USD".
+    /// The pair's base also names the policy parameter holding the asset's
+    /// liquidation threshold ("btc_ltv_threshold").
+    pub fn set_asset_pair(env: Env, asset: Symbol, asset_pair: String) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        env.storage()
             .instance()
-            .get(&DataKey::LoansContract)
-            .ok_or(String::from_str(&env, "Loans contract not set"))?;
+            .set(&DataKey::AssetPair(asset), &asset_pair);
+
+        Ok(())
+    }
 
-        // In production, call loans_contract.get_position(position_id)
-        // For now, we'll return a simplified check
+    /// Check if a position needs liquidation
+    pub fn check_liquidation(env: Env, position_id: BytesN<32>) -> Result<bool, Error> {
+        let position = Self::loans(&env)?
+            .get_position(&position_id)
+            .ok_or(Error::PositionNotFound)?;
+        let asset_pair = Self::asset_pair(&env, &position.asset)?;
 
-        // Get current LTV from price oracle
         let current_ltv = Self::get_position_ltv(&env, &position_id)?;
-
-        // Get liquidation threshold
-        let threshold = Self::get_liquidation_threshold(&env)?;
+        let threshold = Self::get_liquidation_threshold(&env, &asset_pair)?;
 
         Ok(current_ltv > threshold)
     }
 
-    /// Create liquidation intent
+    /// Create liquidation intent (operator only). Returns the intent id, a
+    /// sha256 of the position id, `nonce` and ledger sequence.
     pub fn create_intent(
         env: Env,
-        position_id: String,
+        position_id: BytesN<32>,
+        nonce: u64,
         collateral_to_sell: i128,
         min_output: i128,
-    ) -> Result<String, String> {
+    ) -> Result<BytesN<32>, Error> {
+        Self::require_operator(&env)?;
+
+        // One pending intent per position so concurrent intents cannot
+        // oversell its collateral; the current one must be executed, cancelled
+        // or expired first. Nonces are never reused on a position.
+        for existing_id in Self::get_position_intents(env.clone(), position_id.clone()).iter() {
+            let existing = Self::get_intent(env.clone(), existing_id)?;
+            if existing.status == IntentStatus::Pending {
+                return Err(Error::PositionHasActiveIntent);
+            }
+            if existing.nonce == nonce {
+                return Err(Error::DuplicateNonce);
+            }
+        }
+
         // Verify position needs liquidation
         if !Self::check_liquidation(env.clone(), position_id.clone())? {
-            return Err(String::from_str(&env, "Position healthy"));
+            return Err(Error::PositionHealthy);
         }
 
         // Generate intent ID
-        let intent_id = Self::generate_intent_id(&env);
+        let (intent_id, number) = Self::generate_intent_id(&env, &position_id, nonce);
 
         // Create intent with 5-minute deadline
         let deadline = env.ledger().timestamp() + 300;
 
         let intent = LiquidationIntent {
             id: intent_id.clone(),
+            number,
             position_id: position_id.clone(),
+            nonce,
             collateral_to_sell,
             min_output,
             deadline,
@@ -119,7 +202,7 @@ impl LiquidationManagerContract {
             .set(&DataKey::Intent(intent_id.clone()), &intent);
 
         // Add to position intents
-        let mut position_intents: Vec<String> = env
+        let mut position_intents: Vec<BytesN<32>> = env
             .storage()
             .persistent()
             .get(&DataKey::PositionIntents(position_id.clone()))
@@ -127,65 +210,143 @@ impl LiquidationManagerContract {
         position_intents.push_back(intent_id.clone());
         env.storage()
             .persistent()
-            .set(&DataKey::PositionIntents(position_id), &position_intents);
+            .set(&DataKey::PositionIntents(position_id.clone()), &position_intents);
+
+        events::publish(
+            &env,
+            "LiquidationIntent",
+            intent_id.clone(),
+            LiquidationIntentEvent {
+                position_id,
+                collateral_to_sell,
+                min_out: min_output,
+                deadline,
+            },
+        );
 
         Ok(intent_id)
     }
 
-    /// Execute liquidation intent
+    /// Execute liquidation intent (operator only)
     pub fn execute_intent(
         env: Env,
-        intent_id: String,
+        intent_id: BytesN<32>,
         amount_received: i128,
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
+        Self::require_operator(&env)?;
+
         let mut intent: LiquidationIntent = env
             .storage()
             .persistent()
             .get(&DataKey::Intent(intent_id.clone()))
-            .ok_or(String::from_str(&env, "Intent not found"))?;
+            .ok_or(Error::IntentNotFound)?;
+
+        if intent.status != IntentStatus::Pending {
+            return Err(Error::IntentNotPending);
+        }
 
-        // Check deadline
+        // Check deadline. Returning an error rolls back any writes, so the
+        // intent is left Pending for `expire_intent` to mark.
         if env.ledger().timestamp() > intent.deadline {
-            intent.status = IntentStatus::Expired;
-            env.storage()
-                .persistent()
-                .set(&DataKey::Intent(intent_id), &intent);
-            return Err(String::from_str(&env, "Intent expired"));
+            return Err(Error::IntentExpired);
         }
 
         // Check min output
         if amount_received < intent.min_output {
-            return Err(String::from_str(&env, "Output below minimum"));
+            return Err(Error::OutputBelowMinimum);
         }
 
         // Mark as executed
         intent.status = IntentStatus::Executed;
         env.storage()
             .persistent()
-            .set(&DataKey::Intent(intent_id), &intent);
+            .set(&DataKey::Intent(intent_id.clone()), &intent);
 
-        // Mark position as liquidated in Loans contract
-        let loans_contract: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::LoansContract)
-            .ok_or(String::from_str(&env, "Loans contract not set"))?;
+        events::publish(
+            &env,
+            "ReceiptAccepted",
+            intent_id,
+            ReceiptAcceptedEvent {
+                position_id: intent.position_id,
+                proceeds: amount_received,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a pending intent (operator only)
+    pub fn cancel_intent(env: Env, intent_id: BytesN<32>) -> Result<(), Error> {
+        Self::require_operator(&env)?;
+
+        let mut intent = Self::get_intent(env.clone(), intent_id.clone())?;
+        if intent.status != IntentStatus::Pending {
+            return Err(Error::IntentNotPending);
+        }
+
+        intent.status = IntentStatus::Cancelled;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Intent(intent_id.clone()), &intent);
+
+        events::publish(
+            &env,
+            "IntentCancelled",
+            intent_id,
+            IntentCancelledEvent {
+                position_id: intent.position_id,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Mark a pending intent past its deadline as expired, freeing its
+    /// position for a new intent. Anyone may call this.
+    pub fn expire_intent(env: Env, intent_id: BytesN<32>) -> Result<(), Error> {
+        let mut intent = Self::get_intent(env.clone(), intent_id.clone())?;
+        if intent.status != IntentStatus::Pending {
+            return Err(Error::IntentNotPending);
+        }
+        if env.ledger().timestamp() <= intent.deadline {
+            return Err(Error::IntentNotExpired);
+        }
 
-        // In production: loans_contract.mark_liquidated(intent.position_id)
+        intent.status = IntentStatus::Expired;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Intent(intent_id.clone()), &intent);
+
+        events::publish(
+            &env,
+            "IntentExpired",
+            intent_id,
+            IntentExpiredEvent {
+                position_id: intent.position_id,
+            },
+        );
 
         Ok(())
     }
 
     /// Get liquidation intent
-    pub fn get_intent(env: Env, intent_id: String) -> Result<LiquidationIntent, String> {
+    pub fn get_intent(env: Env, intent_id: BytesN<32>) -> Result<LiquidationIntent, Error> {
         env.storage()
             .persistent()
             .get(&DataKey::Intent(intent_id))
-            .ok_or(String::from_str(&env, "Intent not found"))
+            .ok_or(Error::IntentNotFound)
+    }
+
+    /// Look up an intent id by its sequential number
+    pub fn get_intent_id(env: Env, number: u64) -> Result<BytesN<32>, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::IntentNumber(number))
+            .ok_or(Error::IntentNotFound)
     }
 
     /// Get all intents for a position
-    pub fn get_position_intents(env: Env, position_id: String) -> Vec<String> {
+    pub fn get_position_intents(env: Env, position_id: BytesN<32>) -> Vec<BytesN<32>> {
         env.storage()
             .persistent()
             .get(&DataKey::PositionIntents(position_id))
@@ -194,7 +355,29 @@ impl LiquidationManagerContract {
 
     // Helper functions
 
-    fn generate_intent_id(env: &Env) -> String {
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    fn require_operator(env: &Env) -> Result<Address, Error> {
+        let operator: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Operator)
+            .ok_or(Error::NotInitialized)?;
+        operator.require_auth();
+        Ok(operator)
+    }
+
+    /// Hash (position id, nonce, ledger sequence) into the intent id and
+    /// index it under the next sequential number
+    fn generate_intent_id(env: &Env, position_id: &BytesN<32>, nonce: u64) -> (BytesN<32>, u64) {
         let next_id: u64 = env
             .storage()
             .instance()
@@ -205,17 +388,113 @@ impl LiquidationManagerContract {
             .instance()
             .set(&DataKey::NextIntentId, &(next_id + 1));
 
-        String::from_str(env, &format!("LIQ-{}", next_id))
+        let mut preimage = Bytes::from_array(env, &position_id.to_array());
+        preimage.extend_from_array(&nonce.to_be_bytes());
+        preimage.extend_from_array(&env.ledger().sequence().to_be_bytes());
+        let intent_id: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::IntentNumber(next_id), &intent_id);
+
+        (intent_id, next_id)
+    }
+
+    /// Debt over attested collateral valued at the oracle price, in basis
+    /// points. Fails if the oracle price is stale.
+    fn get_position_ltv(env: &Env, position_id: &BytesN<32>) -> Result<i128, Error> {
+        let loans = Self::loans(env)?;
+        let position = loans
+            .get_position(position_id)
+            .ok_or(Error::PositionNotFound)?;
+
+        let debt = position.principal + position.accrued_interest;
+        if debt == 0 {
+            return Ok(0);
+        }
+
+        let collateral = loans
+            .get_attestation(position_id)
+            .map(|attestation| attestation.amount)
+            .unwrap_or(0);
+        let price = Self::get_fresh_price(env, &Self::asset_pair(env, &position.asset)?)?;
+
+        let collateral_value = collateral * price / PRICE_SCALE;
+        if collateral_value == 0 {
+            return Ok(i128::MAX);
+        }
+
+        Ok(debt * BPS / collateral_value)
     }
 
-    fn get_position_ltv(env: &Env, _position_id: &String) -> Result<u32, String> {
-        // In production, calculate from position data and current prices
-        // For now, return a mock value
-        Ok(7800) // 78% LTV
+    /// The `<asset>_ltv_threshold` parameter from the policy registry
+    fn get_liquidation_threshold(env: &Env, asset_pair: &String) -> Result<i128, Error> {
+        let policy_registry: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PolicyRegistry)
+            .ok_or(Error::NotInitialized)?;
+
+        let name = Self::threshold_parameter(env, asset_pair)?;
+        PolicyRegistryClient::new(env, &policy_registry)
+            .try_get_parameter(&name)
+            .ok()
+            .and_then(|value| value.ok())
+            .ok_or(Error::ThresholdNotSet)
     }
 
-    fn get_liquidation_threshold(env: &Env) -> Result<u32, String> {
-        // In production, fetch from PolicyRegistry
-        Ok(7500) // 75% threshold
+    /// Current oracle price for a pair. The oracle rejects prices past its
+    /// staleness limit; any failure to read a price is reported as stale.
+    fn get_fresh_price(env: &Env, asset_pair: &String) -> Result<i128, Error> {
+        let price_oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PriceOracle)
+            .ok_or(Error::NotInitialized)?;
+
+        let price_data = PriceOracleClient::new(env, &price_oracle)
+            .try_get_price(asset_pair)
+            .ok()
+            .and_then(|price| price.ok())
+            .ok_or(Error::PriceStale)?;
+
+        if price_data.price <= 0 {
+            return Err(Error::InvalidPrice);
+        }
+        Ok(price_data.price)
+    }
+
+    fn loans(env: &Env) -> Result<LoansClient<'_>, Error> {
+        let loans_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::LoansContract)
+            .ok_or(Error::NotInitialized)?;
+        Ok(LoansClient::new(env, &loans_contract))
+    }
+
+    fn asset_pair(env: &Env, asset: &Symbol) -> Result<String, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AssetPair(asset.clone()))
+            .ok_or(Error::AssetPairNotSet)
+    }
+
+    /// "BTC/USD" -> "btc_ltv_threshold"
+    fn threshold_parameter(env: &Env, asset_pair: &String) -> Result<String, Error> {
+        const SUFFIX: &[u8] = b"_ltv_threshold";
+
+        let len = asset_pair.len() as usize;
+        let mut buf = [0u8; 64];
+        if len + SUFFIX.len() > buf.len() {
+            return Err(Error::AssetPairTooLong);
+        }
+        asset_pair.copy_into_slice(&mut buf[..len]);
+
+        let base_len = buf[..len].iter().position(|b| *b == b'/').unwrap_or(len);
+        buf[..base_len].make_ascii_lowercase();
+        buf[base_len..base_len + SUFFIX.len()].copy_from_slice(SUFFIX);
+
+        Ok(String::from_bytes(env, &buf[..base_len + SUFFIX.len()]))
     }
 }
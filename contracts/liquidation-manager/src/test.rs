@@ -0,0 +1,323 @@
+#![cfg(test)]
+
+use super::*;
+use interfaces::{Attestation, Position, PositionStatus, PriceData};
+use soroban_sdk::testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke};
+use soroban_sdk::{contract, contractimpl, symbol_short, vec, IntoVal, Symbol, Val};
+
+#[contract]
+pub struct MockLoans;
+
+#[contractimpl]
+impl MockLoans {
+    pub fn set_position(env: Env, position_id: BytesN<32>, position: Position, attestation: Attestation) {
+        env.storage().instance().set(&(symbol_short!("pos"), position_id.clone()), &position);
+        env.storage().instance().set(&(symbol_short!("att"), position_id), &attestation);
+    }
+
+    pub fn get_position(env: Env, position_id: BytesN<32>) -> Option<Position> {
+        env.storage().instance().get(&(symbol_short!("pos"), position_id))
+    }
+
+    pub fn get_attestation(env: Env, position_id: BytesN<32>) -> Option<Attestation> {
+        env.storage().instance().get(&(symbol_short!("att"), position_id))
+    }
+}
+
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn get_price(env: Env, asset_pair: String) -> PriceData {
+        PriceData {
+            asset_pair,
+            price: 60_000 * PRICE_SCALE,
+            confidence: 100,
+            timestamp: env.ledger().timestamp(),
+            source: String::from_str(&env, "mock"),
+        }
+    }
+}
+
+#[contract]
+pub struct MockRegistry;
+
+#[contractimpl]
+impl MockRegistry {
+    pub fn get_parameter(_env: Env, _name: String) -> i128 {
+        7_500
+    }
+}
+
+struct Setup {
+    env: Env,
+    manager: LiquidationManagerContractClient<'static>,
+    admin: Address,
+    operator: Address,
+    attacker: Address,
+    position_id: BytesN<32>,
+}
+
+/// Manager wired to mocks holding one position at ~83% LTV against a 75%
+/// threshold, so it is liquidatable
+fn setup() -> Setup {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let attacker = Address::generate(&env);
+
+    let loans = MockLoansClient::new(&env, &env.register_contract(None, MockLoans));
+    let oracle = env.register_contract(None, MockOracle);
+    let registry = env.register_contract(None, MockRegistry);
+    let manager = LiquidationManagerContractClient::new(
+        &env,
+        &env.register_contract(None, LiquidationManagerContract),
+    );
+
+    let position_id = BytesN::from_array(&env, &[1; 32]);
+    let vault_ref = BytesN::from_array(&env, &[2; 32]);
+    loans.set_position(
+        &position_id,
+        &Position {
+            owner: Address::generate(&env),
+            principal: 50_000,
+            accrued_interest: 0,
+            collateral_ref: vault_ref.clone(),
+            asset: symbol_short!("BTC"),
+            ltv_bps: 0,
+            status: PositionStatus::Open,
+            last_oracle_round: 0,
+            nonce: 0,
+            created_at: 0,
+        },
+        &Attestation {
+            custodian: BytesN::from_array(&env, &[3; 32]),
+            amount: 1,
+            vault_ref,
+            timestamp: 0,
+        },
+    );
+
+    manager.initialize(&admin, &operator, &loans.address, &oracle, &registry);
+    as_caller(&env, &manager, &admin, "set_asset_pair", (symbol_short!("BTC"), String::from_str(&env, "BTC/USD")));
+    manager.set_asset_pair(&symbol_short!("BTC"), &String::from_str(&env, "BTC/USD"));
+
+    Setup { env, manager, admin, operator, attacker, position_id }
+}
+
+/// Authorize exactly one call to `fn_name` on the manager as `caller`
+fn as_caller<A: IntoVal<Env, soroban_sdk::Vec<Val>>>(
+    env: &Env,
+    manager: &LiquidationManagerContractClient,
+    caller: &Address,
+    fn_name: &str,
+    args: A,
+) {
+    env.mock_auths(&[MockAuth {
+        address: caller,
+        invoke: &MockAuthInvoke {
+            contract: &manager.address,
+            fn_name,
+            args: args.into_val(env),
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+fn create_intent(s: &Setup) -> BytesN<32> {
+    as_caller(&s.env, &s.manager, &s.operator, "create_intent", (s.position_id.clone(), 1u64, 1i128, 55_000i128));
+    s.manager.create_intent(&s.position_id, &1, &1, &55_000)
+}
+
+#[test]
+fn create_intent_rejects_non_operator() {
+    let s = setup();
+
+    as_caller(&s.env, &s.manager, &s.attacker, "create_intent", (s.position_id.clone(), 1u64, 1i128, 55_000i128));
+    assert!(s.manager.try_create_intent(&s.position_id, &1, &1, &55_000).is_err());
+    assert!(s.manager.get_position_intents(&s.position_id).is_empty());
+
+    let intent_id = create_intent(&s);
+    assert_eq!(s.manager.get_intent(&intent_id).status, IntentStatus::Pending);
+}
+
+#[test]
+fn execute_intent_rejects_non_operator() {
+    let s = setup();
+    let intent_id = create_intent(&s);
+
+    as_caller(&s.env, &s.manager, &s.attacker, "execute_intent", (intent_id.clone(), 60_000i128));
+    assert!(s.manager.try_execute_intent(&intent_id, &60_000).is_err());
+    assert_eq!(s.manager.get_intent(&intent_id).status, IntentStatus::Pending);
+
+    // The admin is not the operator either
+    as_caller(&s.env, &s.manager, &s.admin, "execute_intent", (intent_id.clone(), 60_000i128));
+    assert!(s.manager.try_execute_intent(&intent_id, &60_000).is_err());
+
+    as_caller(&s.env, &s.manager, &s.operator, "execute_intent", (intent_id.clone(), 60_000i128));
+    s.manager.execute_intent(&intent_id, &60_000);
+    assert_eq!(s.manager.get_intent(&intent_id).status, IntentStatus::Executed);
+}
+
+#[test]
+fn cancel_intent_rejects_non_operator() {
+    let s = setup();
+    let intent_id = create_intent(&s);
+
+    as_caller(&s.env, &s.manager, &s.attacker, "cancel_intent", (intent_id.clone(),));
+    assert!(s.manager.try_cancel_intent(&intent_id).is_err());
+    assert_eq!(s.manager.get_intent(&intent_id).status, IntentStatus::Pending);
+}
+
+#[test]
+fn set_operator_rejects_non_admin() {
+    let s = setup();
+
+    as_caller(&s.env, &s.manager, &s.attacker, "set_operator", (s.attacker.clone(),));
+    assert!(s.manager.try_set_operator(&s.attacker).is_err());
+    assert_eq!(s.manager.get_operator(), Some(s.operator.clone()));
+
+    as_caller(&s.env, &s.manager, &s.admin, "set_operator", (s.attacker.clone(),));
+    s.manager.set_operator(&s.attacker);
+    assert_eq!(s.manager.get_operator(), Some(s.attacker));
+}
+
+/// Events published since the log held `since` entries; the test
+/// environment keeps every event from earlier calls
+fn events_since(s: &Setup, since: u32) -> soroban_sdk::Vec<(Address, soroban_sdk::Vec<Val>, Val)> {
+    let all = s.env.events().all();
+    all.slice(since..all.len())
+}
+
+/// The event `name` as the manager publishes it for `intent_id`
+fn event<D: IntoVal<Env, Val>>(
+    s: &Setup,
+    name: &str,
+    intent_id: &BytesN<32>,
+    data: D,
+) -> (Address, soroban_sdk::Vec<Val>, Val) {
+    (
+        s.manager.address.clone(),
+        (
+            Symbol::new(&s.env, "liquidation_manager_contract"),
+            Symbol::new(&s.env, name),
+            EVENT_VERSION,
+            intent_id.clone(),
+        )
+            .into_val(&s.env),
+        data.into_val(&s.env),
+    )
+}
+
+#[test]
+fn create_intent_emits_liquidation_intent() {
+    let s = setup();
+    let since = s.env.events().all().len();
+    let intent_id = create_intent(&s);
+
+    assert_eq!(
+        events_since(&s, since),
+        vec![
+            &s.env,
+            event(
+                &s,
+                "LiquidationIntent",
+                &intent_id,
+                LiquidationIntentEvent {
+                    position_id: s.position_id.clone(),
+                    collateral_to_sell: 1,
+                    min_out: 55_000,
+                    deadline: s.env.ledger().timestamp() + 300,
+                },
+            ),
+        ]
+    );
+}
+
+#[test]
+fn execute_intent_emits_receipt_accepted() {
+    let s = setup();
+    let intent_id = create_intent(&s);
+
+    as_caller(&s.env, &s.manager, &s.operator, "execute_intent", (intent_id.clone(), 60_000i128));
+    let since = s.env.events().all().len();
+    s.manager.execute_intent(&intent_id, &60_000);
+    assert_eq!(
+        events_since(&s, since),
+        vec![
+            &s.env,
+            event(
+                &s,
+                "ReceiptAccepted",
+                &intent_id,
+                ReceiptAcceptedEvent {
+                    position_id: s.position_id.clone(),
+                    proceeds: 60_000,
+                },
+            ),
+        ]
+    );
+}
+
+#[test]
+fn cancel_intent_emits_intent_cancelled() {
+    let s = setup();
+    let intent_id = create_intent(&s);
+
+    as_caller(&s.env, &s.manager, &s.operator, "cancel_intent", (intent_id.clone(),));
+    let since = s.env.events().all().len();
+    s.manager.cancel_intent(&intent_id);
+    assert_eq!(
+        events_since(&s, since),
+        vec![
+            &s.env,
+            event(
+                &s,
+                "IntentCancelled",
+                &intent_id,
+                IntentCancelledEvent {
+                    position_id: s.position_id.clone(),
+                },
+            ),
+        ]
+    );
+}
+
+#[test]
+fn expire_intent_emits_intent_expired() {
+    let s = setup();
+    let intent_id = create_intent(&s);
+
+    s.env.ledger().with_mut(|ledger| ledger.timestamp += 301);
+    let since = s.env.events().all().len();
+    s.manager.expire_intent(&intent_id);
+    assert_eq!(
+        events_since(&s, since),
+        vec![
+            &s.env,
+            event(
+                &s,
+                "IntentExpired",
+                &intent_id,
+                IntentExpiredEvent {
+                    position_id: s.position_id.clone(),
+                },
+            ),
+        ]
+    );
+}
+
+#[test]
+fn failed_calls_emit_nothing() {
+    let s = setup();
+    let intent_id = create_intent(&s);
+
+    let since = s.env.events().all().len();
+    as_caller(&s.env, &s.manager, &s.operator, "execute_intent", (intent_id.clone(), 1i128));
+    assert_eq!(
+        s.manager.try_execute_intent(&intent_id, &1),
+        Err(Ok(Error::OutputBelowMinimum))
+    );
+    assert!(events_since(&s, since).is_empty());
+}
@@ -0,0 +1,352 @@
+use soroban_sdk::{contractimpl, contracttype, token, Address, BytesN, Env, Map, Symbol, Vec};
+
+use crate::*;
+
+// Dutch auction liquidation mode: a claim on part of a position's collateral
+// is offered on-chain at a price that starts above the oracle price and falls
+// linearly to the policy's slippage floor. Whitelisted bidders pay stablecoin
+// and every fill is applied to the loan immediately. Each bidder's fills are
+// recorded as a claim that the admin marks settled once the custodian has
+// delivered the collateral.
+
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum AuctionStatus {
+    Active,
+    Filled,
+    Closed,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Auction {
+    pub position_id: BytesN<32>,
+    pub asset: Symbol,
+    pub collateral_amount: i128,
+    pub collateral_remaining: i128,
+    pub start_price: i128,
+    pub floor_price: i128,
+    pub oracle_round: i128,
+    pub started_at: u64,
+    pub duration_seconds: u64,
+    pub proceeds: i128,
+    pub nonce: i128,
+    pub status: AuctionStatus,
+}
+
+/// Collateral a bidder has bought from one auction
+#[contracttype]
+#[derive(Clone)]
+pub struct AuctionClaim {
+    pub collateral_amount: i128,
+    pub cost: i128,
+    pub collateral_delivered: i128,
+}
+
+#[contractimpl]
+impl LiquidationManager {
+    /// Offer `collateral_amount` of a position's collateral in a Dutch auction
+    /// (admin only). The price starts `start_premium_bps` above the oracle
+    /// price and decays to the policy's `max_slippage_bps` floor over
+    /// `duration_seconds`. The same checks as intents apply: current
+    /// `policy_version`, a liquidation band, no cooldown, and no live intent
+    /// or running auction on the position. `collateral_amount` cannot exceed
+    /// the attested collateral not yet sold.
+    pub fn start_auction(
+        env: Env,
+        auction_id: BytesN<32>,
+        position_id: BytesN<32>,
+        collateral_amount: i128,
+        start_premium_bps: i128,
+        duration_seconds: u64,
+        policy_version: i128
+    ) {
+        Self::require_admin(&env);
+
+        if collateral_amount <= 0 || start_premium_bps < 0 || duration_seconds == 0 {
+            panic!("invalid auction parameters");
+        }
+
+        let LiquidationTarget { position, policy, .. } = Self::begin_liquidation(&env, &position_id, policy_version);
+        let attestation = Self::loans(&env).get_attestation(&position_id).expect("collateral not attested");
+        if collateral_amount > attestation.amount {
+            panic!("collateral exceeds attestation");
+        }
+        let round = Self::fresh_price(&env, &position.asset, &policy);
+
+        let mut auctions: Map<BytesN<32>, Auction> = env.storage()
+            .instance()
+            .get(&DataKey::Auctions)
+            .unwrap_or(Map::new(&env));
+
+        if auctions.contains_key(auction_id.clone()) {
+            panic!("auction already exists");
+        }
+
+        let nonce: i128 = env.storage().instance().get(&DataKey::NextNonce).unwrap_or(1i128);
+        env.storage().instance().set(&DataKey::NextNonce, &(nonce + 1));
+
+        let auction = Auction {
+            position_id: position_id.clone(),
+            asset: position.asset,
+            collateral_amount,
+            collateral_remaining: collateral_amount,
+            start_price: round.price * (BPS + start_premium_bps) / BPS,
+            floor_price: round.price * (BPS - policy.max_slippage_bps) / BPS,
+            oracle_round: round.round_id,
+            started_at: env.ledger().timestamp(),
+            duration_seconds,
+            proceeds: 0,
+            nonce,
+            status: AuctionStatus::Active,
+        };
+
+        auctions.set(auction_id.clone(), auction.clone());
+        env.storage().instance().set(&DataKey::Auctions, &auctions);
+
+        let position_key = DataKey::PositionAuctions(position_id.clone());
+        let mut position_auctions = Self::get_position_auctions(env.clone(), position_id.clone());
+        position_auctions.push_back(auction_id.clone());
+        env.storage().persistent().set(&position_key, &position_auctions);
+
+        events::publish(
+            &env,
+            "AuctionStarted",
+            auction_id,
+            AuctionStartedEvent {
+                position_id,
+                collateral_amount,
+                start_price: auction.start_price,
+                floor_price: auction.floor_price,
+                ends_at: auction.started_at + duration_seconds,
+            },
+        );
+    }
+
+    /// Buy up to `collateral_amount` from an active auction at the current
+    /// price. The fill is capped where its cost, net of the liquidation
+    /// penalty, clears the position's outstanding debt; the cost is paid in
+    /// stablecoin, less the insurance share of the penalty, to the admin
+    /// treasury and applied to the loan, and the collateral is added to the
+    /// bidder's claim on the auction. Refused while the asset's circuit
+    /// breaker is on.
+    /// Returns the stablecoin amount paid.
+    pub fn bid(env: Env, auction_id: BytesN<32>, bidder: Address, collateral_amount: i128) -> i128 {
+        bidder.require_auth();
+
+        if !Self::is_bidder(env.clone(), bidder.clone()) {
+            panic!("bidder not allowed");
+        }
+        if collateral_amount <= 0 {
+            panic!("invalid amount");
+        }
+
+        let mut auctions: Map<BytesN<32>, Auction> = env.storage()
+            .instance()
+            .get(&DataKey::Auctions)
+            .unwrap_or(Map::new(&env));
+
+        let mut auction = auctions.get(auction_id.clone()).expect("auction not found");
+        if auction.status != AuctionStatus::Active {
+            panic!("auction not active");
+        }
+
+        let now = env.ledger().timestamp();
+        if now > auction.started_at + auction.duration_seconds {
+            panic!("auction ended");
+        }
+        if Self::policy(&env, &auction.asset).circuit_breaker {
+            panic!("circuit breaker active");
+        }
+
+        let price = Self::auction_price(&auction, now);
+        let position = Self::loans(&env).get_position(&auction.position_id).expect("position not found");
+        let debt = position.principal + position.accrued_interest;
+
+        // Enough collateral for the loan to receive the whole debt once the
+        // penalty is withheld, rounded up at each step
+        let gross = Self::gross_for_net(&env, &auction.asset, debt);
+        let amount = collateral_amount
+            .min(auction.collateral_remaining)
+            .min((gross * PRICE_SCALE + price - 1) / price);
+        let cost = amount * price / PRICE_SCALE;
+        if amount <= 0 || cost <= 0 {
+            panic!("nothing to fill");
+        }
+        let split = Self::penalty_split(&env, &auction.asset, cost);
+
+        auction.collateral_remaining -= amount;
+        auction.proceeds += cost;
+        if auction.collateral_remaining == 0 || cost - split.penalty >= debt {
+            auction.status = AuctionStatus::Filled;
+        }
+        auctions.set(auction_id.clone(), auction.clone());
+        env.storage().instance().set(&DataKey::Auctions, &auctions);
+
+        let mut claims = Self::get_auction_claims(env.clone(), auction_id.clone());
+        let mut claim = claims.get(bidder.clone()).unwrap_or(AuctionClaim {
+            collateral_amount: 0,
+            cost: 0,
+            collateral_delivered: 0,
+        });
+        claim.collateral_amount += amount;
+        claim.cost += cost;
+        claims.set(bidder.clone(), claim);
+        env.storage().persistent().set(&DataKey::AuctionClaims(auction_id.clone()), &claims);
+
+        let config = Self::get_liquidation_config(env.clone()).expect("liquidation config not set");
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("admin not set");
+        let settlement = Settlement {
+            proceeds: cost,
+            collateral_sold: amount,
+            oracle_round: auction.oracle_round,
+            submitter: bidder.clone(),
+            payer: bidder.clone(),
+            split,
+        };
+        let paid = Self::settle(&env, &auction.position_id, auction.nonce, &settlement, Some(admin.clone()));
+        token::Client::new(&env, &config.stablecoin).transfer(&bidder, &admin, &(cost - paid));
+        Self::cover_shortfall(&env, &auction.position_id, auction.oracle_round, auction.nonce);
+
+        events::publish(
+            &env,
+            "AuctionFilled",
+            auction_id,
+            AuctionFilledEvent {
+                position_id: auction.position_id.clone(),
+                bidder,
+                collateral_amount: amount,
+                price,
+                cost,
+            },
+        );
+
+        Self::start_cooldown(&env, &auction.position_id);
+        cost
+    }
+
+    /// Close an auction early or after it ends (admin only)
+    pub fn close_auction(env: Env, auction_id: BytesN<32>) {
+        Self::require_admin(&env);
+
+        let mut auctions: Map<BytesN<32>, Auction> = env.storage()
+            .instance()
+            .get(&DataKey::Auctions)
+            .unwrap_or(Map::new(&env));
+
+        let mut auction = auctions.get(auction_id.clone()).expect("auction not found");
+        if auction.status == AuctionStatus::Active {
+            auction.status = AuctionStatus::Closed;
+            auctions.set(auction_id.clone(), auction.clone());
+            env.storage().instance().set(&DataKey::Auctions, &auctions);
+
+            events::publish(
+                &env,
+                "AuctionClosed",
+                auction_id,
+                AuctionClosedEvent {
+                    position_id: auction.position_id,
+                    collateral_remaining: auction.collateral_remaining,
+                    proceeds: auction.proceeds,
+                },
+            );
+        }
+    }
+
+    /// Record that the custodian has delivered a bidder's outstanding
+    /// collateral from an auction (admin only)
+    pub fn settle_claim(env: Env, auction_id: BytesN<32>, bidder: Address) {
+        Self::require_admin(&env);
+
+        let mut claims = Self::get_auction_claims(env.clone(), auction_id.clone());
+        let mut claim = claims.get(bidder.clone()).expect("claim not found");
+        let outstanding = claim.collateral_amount - claim.collateral_delivered;
+        if outstanding <= 0 {
+            panic!("claim already settled");
+        }
+
+        claim.collateral_delivered = claim.collateral_amount;
+        claims.set(bidder.clone(), claim);
+        env.storage().persistent().set(&DataKey::AuctionClaims(auction_id.clone()), &claims);
+
+        events::publish(
+            &env,
+            "ClaimSettled",
+            auction_id,
+            ClaimSettledEvent { bidder, collateral_amount: outstanding },
+        );
+    }
+
+    /// Every bidder's claim on an auction (view function)
+    pub fn get_auction_claims(env: Env, auction_id: BytesN<32>) -> Map<Address, AuctionClaim> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AuctionClaims(auction_id))
+            .unwrap_or(Map::new(&env))
+    }
+
+    pub fn get_auction(env: Env, auction_id: BytesN<32>) -> Option<Auction> {
+        let auctions: Map<BytesN<32>, Auction> = env.storage()
+            .instance()
+            .get(&DataKey::Auctions)
+            .unwrap_or(Map::new(&env));
+        auctions.get(auction_id)
+    }
+
+    /// Every auction started for a position, oldest first
+    pub fn get_position_auctions(env: Env, position_id: BytesN<32>) -> Vec<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PositionAuctions(position_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Current unit price of an auction's collateral (scaled by 1e7)
+    pub fn get_auction_price(env: Env, auction_id: BytesN<32>) -> i128 {
+        let auction = Self::get_auction(env.clone(), auction_id).expect("auction not found");
+        Self::auction_price(&auction, env.ledger().timestamp())
+    }
+
+    pub fn add_bidder(env: Env, bidder: Address) {
+        Self::require_admin(&env);
+
+        let mut bidders: Vec<Address> = env.storage()
+            .instance()
+            .get(&DataKey::AuctionBidders)
+            .unwrap_or(Vec::new(&env));
+
+        if !bidders.contains(&bidder) {
+            bidders.push_back(bidder);
+            env.storage().instance().set(&DataKey::AuctionBidders, &bidders);
+        }
+    }
+
+    pub fn remove_bidder(env: Env, bidder: Address) {
+        Self::require_admin(&env);
+
+        let mut bidders: Vec<Address> = env.storage()
+            .instance()
+            .get(&DataKey::AuctionBidders)
+            .unwrap_or(Vec::new(&env));
+
+        if let Some(index) = bidders.first_index_of(&bidder) {
+            bidders.remove(index);
+            env.storage().instance().set(&DataKey::AuctionBidders, &bidders);
+        }
+    }
+
+    pub fn is_bidder(env: Env, bidder: Address) -> bool {
+        let bidders: Vec<Address> = env.storage()
+            .instance()
+            .get(&DataKey::AuctionBidders)
+            .unwrap_or(Vec::new(&env));
+        bidders.contains(&bidder)
+    }
+
+    /// Linear decay from `start_price` to `floor_price` over the auction window
+    fn auction_price(auction: &Auction, now: u64) -> i128 {
+        let elapsed = now.saturating_sub(auction.started_at).min(auction.duration_seconds);
+        let drop = (auction.start_price - auction.floor_price) * elapsed as i128 / auction.duration_seconds as i128;
+        auction.start_price - drop
+    }
+}
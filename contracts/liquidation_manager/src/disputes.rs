@@ -0,0 +1,269 @@
+use soroban_sdk::{contractimpl, contracttype, token, Address, BytesN, Env, Map, Symbol};
+
+use crate::*;
+
+// Optional dispute mode for liquidation receipts. When the policy registry
+// sets a dispute window for an asset, accepted receipts are held as Pending
+// for that window and their penalty is escrowed here. The guardian may reject
+// a receipt outright; a borrower's challenge is held for the guardian to
+// adjudicate. Receipts that are not challenged are applied to the loan by
+// `finalize_receipt`, and the admin or guardian can discard stale ones.
+
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum ReceiptStatus {
+    Pending,
+    Challenged,
+    Rejected,
+    Settled,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PendingReceipt {
+    pub proceeds: i128,
+    pub collateral_sold: i128,
+    pub executed_oracle_round: i128,
+    pub submitter: Address,
+    pub submitted_at: u64,
+    pub challenge_deadline: u64,
+    pub status: ReceiptStatus,
+    pub challenger: Option<Address>,
+    pub penalty: i128,
+    pub reward: i128,
+    pub insurance: i128,
+}
+
+#[contractimpl]
+impl LiquidationManager {
+    /// Guardian allowed to reject receipts and adjudicate borrower challenges (admin only)
+    pub fn set_guardian(env: Env, guardian: Address) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+    }
+
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Guardian)
+    }
+
+    /// Challenge a pending receipt before its window closes. A challenge by
+    /// the guardian rejects the receipt; one by the position's owner holds it
+    /// as Challenged until the guardian calls `resolve_challenge`.
+    pub fn challenge_receipt(env: Env, intent_id: BytesN<32>, challenger: Address, reason: Symbol) {
+        challenger.require_auth();
+
+        let intent = Self::get_intent(env.clone(), intent_id.clone()).expect("intent not found");
+        let position = Self::loans(&env).get_position(&intent.position_id).expect("position not found");
+        let is_guardian = Self::get_guardian(env.clone()) == Some(challenger.clone());
+        if !is_guardian && position.owner != challenger {
+            panic!("not authorized to challenge");
+        }
+
+        let mut receipts = Self::pending_receipts(&env);
+        let mut receipt = receipts.get(intent_id.clone()).expect("no pending receipt");
+        if receipt.status != ReceiptStatus::Pending {
+            panic!("receipt not pending");
+        }
+        if env.ledger().timestamp() > receipt.challenge_deadline {
+            panic!("dispute window closed");
+        }
+
+        receipt.status = ReceiptStatus::Challenged;
+        receipt.challenger = Some(challenger.clone());
+        receipts.set(intent_id.clone(), receipt.clone());
+        env.storage().instance().set(&DataKey::PendingReceipts, &receipts);
+
+        events::publish(
+            &env,
+            "ReceiptChallenged",
+            intent_id.clone(),
+            ReceiptChallengedEvent {
+                position_id: intent.position_id.clone(),
+                submitter: receipt.submitter.clone(),
+                challenger,
+                reason,
+            },
+        );
+
+        if is_guardian {
+            Self::reject_receipt(&env, &intent_id, &intent.position_id, receipt);
+        }
+    }
+
+    /// Rule on a borrower's challenge (guardian only). An upheld challenge
+    /// rejects the receipt and refunds the escrowed penalty; otherwise the
+    /// receipt is applied to the loan.
+    pub fn resolve_challenge(env: Env, intent_id: BytesN<32>, upheld: bool) {
+        let guardian = Self::get_guardian(env.clone()).expect("guardian not set");
+        guardian.require_auth();
+
+        let receipt = Self::pending_receipts(&env).get(intent_id.clone()).expect("no pending receipt");
+        if receipt.status != ReceiptStatus::Challenged {
+            panic!("receipt not challenged");
+        }
+
+        let intent = Self::get_intent(env.clone(), intent_id.clone()).expect("intent not found");
+        if upheld {
+            Self::reject_receipt(&env, &intent_id, &intent.position_id, receipt);
+        } else {
+            Self::settle_receipt(&env, &intent_id, intent, receipt);
+        }
+    }
+
+    /// Discard a pending or challenged receipt that is no longer wanted, e.g.
+    /// one left waiting on a stale challenge (admin or guardian). The escrowed
+    /// penalty is refunded and the intent can take a new receipt.
+    pub fn discard_receipt(env: Env, caller: Address, intent_id: BytesN<32>) {
+        caller.require_auth();
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("admin not set");
+        if caller != admin && Self::get_guardian(env.clone()) != Some(caller) {
+            panic!("not authorized");
+        }
+
+        let receipt = Self::pending_receipts(&env).get(intent_id.clone()).expect("no pending receipt");
+        if receipt.status != ReceiptStatus::Pending && receipt.status != ReceiptStatus::Challenged {
+            panic!("receipt not pending");
+        }
+
+        let intent = Self::get_intent(env.clone(), intent_id.clone()).expect("intent not found");
+        Self::reject_receipt(&env, &intent_id, &intent.position_id, receipt);
+    }
+
+    /// Apply a pending receipt to the loan once its dispute window has passed
+    /// without a challenge. Anyone may call this; the penalty split is paid
+    /// from the escrow taken when the receipt was submitted. Refused while the
+    /// intent's venue is delisted or the asset's circuit breaker is on.
+    pub fn finalize_receipt(env: Env, intent_id: BytesN<32>) {
+        let receipt = Self::pending_receipts(&env).get(intent_id.clone()).expect("no pending receipt");
+        if receipt.status != ReceiptStatus::Pending {
+            panic!("receipt not pending");
+        }
+        if env.ledger().timestamp() <= receipt.challenge_deadline {
+            panic!("dispute window open");
+        }
+
+        let intent = Self::get_intent(env.clone(), intent_id.clone()).expect("intent not found");
+        Self::settle_receipt(&env, &intent_id, intent, receipt);
+    }
+
+    /// Latest receipt held for dispute on an intent, in any status (view function)
+    pub fn get_pending_receipt(env: Env, intent_id: BytesN<32>) -> Option<PendingReceipt> {
+        Self::pending_receipts(&env).get(intent_id)
+    }
+
+    /// Stablecoin held by this contract as escrowed penalties of receipts
+    /// awaiting settlement, apart from executor bonds (view function)
+    pub fn get_total_escrowed(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::EscrowTotal).unwrap_or(0i128)
+    }
+
+    /// Add escrow taken for a held receipt, or release it with a negative
+    /// `delta` when the receipt is settled or rejected
+    pub(crate) fn adjust_escrow(env: &Env, delta: i128) {
+        let total = Self::get_total_escrowed(env.clone()) + delta;
+        if total < 0 {
+            panic!("escrow exceeded");
+        }
+        env.storage().instance().set(&DataKey::EscrowTotal, &total);
+    }
+
+    pub(crate) fn hold_receipt(env: &Env, intent_id: &BytesN<32>, position_id: &BytesN<32>, receipt: PendingReceipt) {
+        let mut receipts = Self::pending_receipts(env);
+        receipts.set(intent_id.clone(), receipt.clone());
+        env.storage().instance().set(&DataKey::PendingReceipts, &receipts);
+
+        events::publish(
+            env,
+            "ReceiptPending",
+            intent_id.clone(),
+            ReceiptPendingEvent {
+                position_id: position_id.clone(),
+                proceeds: receipt.proceeds,
+                collateral_sold: receipt.collateral_sold,
+                challenge_deadline: receipt.challenge_deadline,
+            },
+        );
+    }
+
+    /// Whether an intent has a receipt waiting out its dispute window or an
+    /// adjudication
+    pub(crate) fn has_pending_receipt(env: &Env, intent_id: &BytesN<32>) -> bool {
+        match Self::pending_receipts(env).get(intent_id.clone()) {
+            Some(receipt) => receipt.status == ReceiptStatus::Pending || receipt.status == ReceiptStatus::Challenged,
+            None => false,
+        }
+    }
+
+    /// Mark a held receipt Settled and apply it, paying its penalty split
+    /// out of the escrow. The venue and circuit breaker are checked again as
+    /// either may have changed during the dispute window.
+    fn settle_receipt(env: &Env, intent_id: &BytesN<32>, intent: LiquidationIntent, mut receipt: PendingReceipt) {
+        if !intent.status.is_live() {
+            panic!("intent not open");
+        }
+        Self::require_venue_allowed(env, &intent.venue_hash);
+        if Self::policy(env, &intent.asset).circuit_breaker {
+            panic!("circuit breaker active");
+        }
+
+        receipt.status = ReceiptStatus::Settled;
+        Self::store_receipt(env, intent_id, &receipt);
+        Self::adjust_escrow(env, -receipt.penalty);
+
+        let settlement = Settlement {
+            proceeds: receipt.proceeds,
+            collateral_sold: receipt.collateral_sold,
+            oracle_round: receipt.executed_oracle_round,
+            submitter: receipt.submitter,
+            payer: env.current_contract_address(),
+            split: PenaltySplit {
+                penalty: receipt.penalty,
+                reward: receipt.reward,
+                insurance: receipt.insurance,
+            },
+        };
+        Self::apply_receipt(env, intent_id, intent, settlement);
+    }
+
+    /// Mark a held receipt Rejected and refund its escrowed penalty to the
+    /// submitter
+    fn reject_receipt(env: &Env, intent_id: &BytesN<32>, position_id: &BytesN<32>, mut receipt: PendingReceipt) {
+        receipt.status = ReceiptStatus::Rejected;
+        Self::store_receipt(env, intent_id, &receipt);
+
+        if receipt.penalty > 0 {
+            Self::adjust_escrow(env, -receipt.penalty);
+            let config = Self::get_liquidation_config(env.clone()).expect("liquidation config not set");
+            token::Client::new(env, &config.stablecoin).transfer(
+                &env.current_contract_address(),
+                &receipt.submitter,
+                &receipt.penalty,
+            );
+        }
+
+        events::publish(
+            env,
+            "ReceiptRejected",
+            intent_id.clone(),
+            ReceiptRejectedEvent {
+                position_id: position_id.clone(),
+                submitter: receipt.submitter,
+                refunded: receipt.penalty,
+            },
+        );
+    }
+
+    fn store_receipt(env: &Env, intent_id: &BytesN<32>, receipt: &PendingReceipt) {
+        let mut receipts = Self::pending_receipts(env);
+        receipts.set(intent_id.clone(), receipt.clone());
+        env.storage().instance().set(&DataKey::PendingReceipts, &receipts);
+    }
+
+    fn pending_receipts(env: &Env) -> Map<BytesN<32>, PendingReceipt> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PendingReceipts)
+            .unwrap_or(Map::new(env))
+    }
+}
@@ -0,0 +1,161 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, Symbol, Val};
+
+/// Schema version in every event topic, bumped when a payload changes shape
+pub const EVENT_VERSION: u32 = 1;
+
+const CONTRACT: &str = "liquidation_manager";
+
+/// Publish `data` under `(contract, event name, EVENT_VERSION, primary id)`
+pub(crate) fn publish<I, D>(env: &Env, name: &str, id: I, data: D)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    env.events().publish(
+        (Symbol::new(env, CONTRACT), Symbol::new(env, name), EVENT_VERSION, id),
+        data,
+    );
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationIntentEvent {
+    pub position_id: BytesN<32>,
+    pub notional_to_raise: i128,
+    pub min_out: i128,
+    pub deadline: u64,
+    pub nonce: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptAcceptedEvent {
+    pub position_id: BytesN<32>,
+    pub proceeds: i128,
+    pub oracle_round: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntentCancelledEvent {
+    pub position_id: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CooldownStartedEvent {
+    pub started_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionStartedEvent {
+    pub position_id: BytesN<32>,
+    pub collateral_amount: i128,
+    pub start_price: i128,
+    pub floor_price: i128,
+    pub ends_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionFilledEvent {
+    pub position_id: BytesN<32>,
+    pub bidder: Address,
+    pub collateral_amount: i128,
+    pub price: i128,
+    pub cost: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuctionClosedEvent {
+    pub position_id: BytesN<32>,
+    pub collateral_remaining: i128,
+    pub proceeds: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimSettledEvent {
+    pub bidder: Address,
+    pub collateral_amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntentExpiredEvent {
+    pub position_id: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondUpdatedEvent {
+    pub amount: i128,
+    pub bond: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntentAssignedEvent {
+    pub executor: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutorSlashedEvent {
+    pub intent_id: BytesN<32>,
+    pub amount: i128,
+    pub reason: Symbol,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntentFilledEvent {
+    pub position_id: BytesN<32>,
+    pub proceeds_raised: i128,
+    pub collateral_sold: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PenaltyChargedEvent {
+    pub proceeds: i128,
+    pub penalty: i128,
+    pub reward: i128,
+    pub reward_to: Option<Address>,
+    pub insurance: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShortfallCoveredEvent {
+    pub shortfall: i128,
+    pub covered: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptPendingEvent {
+    pub position_id: BytesN<32>,
+    pub proceeds: i128,
+    pub collateral_sold: i128,
+    pub challenge_deadline: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptChallengedEvent {
+    pub position_id: BytesN<32>,
+    pub submitter: Address,
+    pub challenger: Address,
+    pub reason: Symbol,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReceiptRejectedEvent {
+    pub position_id: BytesN<32>,
+    pub submitter: Address,
+    pub refunded: i128,
+}
@@ -0,0 +1,242 @@
+use soroban_sdk::{contractimpl, contracttype, token, Address, BytesN, Env, Map, Symbol, Vec};
+
+use crate::*;
+
+// Registry of off-chain liquidation executors. Executors post a stablecoin
+// bond held by this contract, are assigned intents by the admin, submit the
+// receipts for those intents, and can be slashed by governance for false
+// receipts or missed deadlines. Each released assignment keeps the bond
+// locked for a challenge period so it is still there to slash if the receipt
+// later turns out to be false. Bonds are totalled apart from the dispute
+// penalties this contract also escrows, so neither pays out of the other.
+
+/// Challenge period applied until the admin sets one
+const DEFAULT_BOND_LOCK_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone)]
+pub struct ExecutorRecord {
+    pub bond: i128,
+    pub active_assignments: Vec<BytesN<32>>,
+    pub completed: u32,
+    pub slash_count: u32,
+    pub slashed_total: i128,
+    pub registered_at: u64,
+    pub locked_until: u64,
+}
+
+#[contractimpl]
+impl LiquidationManager {
+    /// Minimum bond an executor must hold to be assigned intents (admin only)
+    pub fn set_min_executor_bond(env: Env, amount: i128) {
+        Self::require_admin(&env);
+        if amount < 0 {
+            panic!("invalid amount");
+        }
+        env.storage().instance().set(&DataKey::MinExecutorBond, &amount);
+    }
+
+    /// How long a bond stays locked after each settled, expired, cancelled or
+    /// slashed assignment (admin only)
+    pub fn set_bond_lock_period(env: Env, seconds: u64) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::BondLockSeconds, &seconds);
+    }
+
+    pub fn get_bond_lock_period(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::BondLockSeconds)
+            .unwrap_or(DEFAULT_BOND_LOCK_SECONDS)
+    }
+
+    /// Register as an executor, or top up an existing bond, by transferring
+    /// `amount` of stablecoin into this contract
+    pub fn post_bond(env: Env, executor: Address, amount: i128) {
+        executor.require_auth();
+        if amount <= 0 {
+            panic!("invalid amount");
+        }
+
+        let config = Self::get_liquidation_config(env.clone()).expect("liquidation config not set");
+        token::Client::new(&env, &config.stablecoin).transfer(&executor, &env.current_contract_address(), &amount);
+
+        let mut executors = Self::executors(&env);
+        let mut record = executors.get(executor.clone()).unwrap_or(ExecutorRecord {
+            bond: 0,
+            active_assignments: Vec::new(&env),
+            completed: 0,
+            slash_count: 0,
+            slashed_total: 0,
+            registered_at: env.ledger().timestamp(),
+            locked_until: 0,
+        });
+        record.bond += amount;
+        executors.set(executor.clone(), record.clone());
+        env.storage().instance().set(&DataKey::Executors, &executors);
+        Self::adjust_bond_total(&env, amount);
+
+        events::publish(&env, "BondPosted", executor, BondUpdatedEvent { amount, bond: record.bond });
+    }
+
+    /// Withdraw bond. Not allowed while the executor has active assignments
+    /// or within the lock period after its last released assignment.
+    pub fn withdraw_bond(env: Env, executor: Address, amount: i128) {
+        executor.require_auth();
+
+        let mut executors = Self::executors(&env);
+        let mut record = executors.get(executor.clone()).expect("executor not registered");
+        if !record.active_assignments.is_empty() {
+            panic!("executor has active assignments");
+        }
+        if env.ledger().timestamp() < record.locked_until {
+            panic!("bond locked");
+        }
+        if amount <= 0 || amount > record.bond {
+            panic!("invalid amount");
+        }
+
+        record.bond -= amount;
+        executors.set(executor.clone(), record.clone());
+        env.storage().instance().set(&DataKey::Executors, &executors);
+        Self::adjust_bond_total(&env, -amount);
+
+        let config = Self::get_liquidation_config(env.clone()).expect("liquidation config not set");
+        token::Client::new(&env, &config.stablecoin).transfer(&env.current_contract_address(), &executor, &amount);
+
+        events::publish(&env, "BondWithdrawn", executor, BondUpdatedEvent { amount, bond: record.bond });
+    }
+
+    /// Assign a live intent to a bonded executor (admin only). Only the
+    /// assigned executor may then submit its receipt.
+    pub fn assign_intent(env: Env, intent_id: BytesN<32>, executor: Address) {
+        Self::require_admin(&env);
+
+        let intent = Self::get_intent(env.clone(), intent_id.clone()).expect("intent not found");
+        if !intent.status.is_live() {
+            panic!("intent not open");
+        }
+        if Self::get_assigned_executor(env.clone(), intent_id.clone()).is_some() {
+            panic!("intent already assigned");
+        }
+
+        let mut executors = Self::executors(&env);
+        let mut record = executors.get(executor.clone()).expect("executor not registered");
+        let min_bond: i128 = env.storage().instance().get(&DataKey::MinExecutorBond).unwrap_or(0i128);
+        if record.bond < min_bond || record.bond == 0 {
+            panic!("insufficient bond");
+        }
+
+        record.active_assignments.push_back(intent_id.clone());
+        executors.set(executor.clone(), record);
+        env.storage().instance().set(&DataKey::Executors, &executors);
+
+        let mut assignments = Self::assignments(&env);
+        assignments.set(intent_id.clone(), executor.clone());
+        env.storage().instance().set(&DataKey::Assignments, &assignments);
+
+        events::publish(&env, "IntentAssigned", intent_id, IntentAssignedEvent { executor });
+    }
+
+    /// Slash `amount` of an executor's bond to the admin treasury (governance
+    /// only), e.g. after a receipt is proven false or a deadline was missed.
+    /// Any assignment of `intent_id` to the executor is released.
+    pub fn slash_executor(env: Env, executor: Address, intent_id: BytesN<32>, amount: i128, reason: Symbol) {
+        let admin = Self::require_admin(&env);
+
+        let mut executors = Self::executors(&env);
+        let mut record = executors.get(executor.clone()).expect("executor not registered");
+        if amount <= 0 || amount > record.bond {
+            panic!("invalid amount");
+        }
+
+        record.bond -= amount;
+        record.slash_count += 1;
+        record.slashed_total += amount;
+        if let Some(index) = record.active_assignments.first_index_of(&intent_id) {
+            record.active_assignments.remove(index);
+            Self::lock_bond(&env, &mut record);
+
+            let mut assignments = Self::assignments(&env);
+            assignments.remove(intent_id.clone());
+            env.storage().instance().set(&DataKey::Assignments, &assignments);
+        }
+        executors.set(executor.clone(), record);
+        env.storage().instance().set(&DataKey::Executors, &executors);
+        Self::adjust_bond_total(&env, -amount);
+
+        let config = Self::get_liquidation_config(env.clone()).expect("liquidation config not set");
+        token::Client::new(&env, &config.stablecoin).transfer(&env.current_contract_address(), &admin, &amount);
+
+        events::publish(
+            &env,
+            "ExecutorSlashed",
+            executor,
+            ExecutorSlashedEvent { intent_id, amount, reason },
+        );
+    }
+
+    /// Bond, track record and active assignments of an executor (view function)
+    pub fn get_executor(env: Env, executor: Address) -> Option<ExecutorRecord> {
+        Self::executors(&env).get(executor)
+    }
+
+    /// Stablecoin held by this contract as executor bonds (view function)
+    pub fn get_total_bonded(env: Env) -> i128 {
+        env.storage().instance().get(&DataKey::BondTotal).unwrap_or(0i128)
+    }
+
+    pub fn get_assigned_executor(env: Env, intent_id: BytesN<32>) -> Option<Address> {
+        Self::assignments(&env).get(intent_id)
+    }
+
+    /// Release an intent's assignment once it is no longer live and lock the
+    /// executor's bond for the challenge period. Settled intents count towards
+    /// the executor's completed record.
+    pub(crate) fn release_assignment(env: &Env, intent_id: &BytesN<32>, completed: bool) {
+        let mut assignments = Self::assignments(env);
+        let executor = match assignments.get(intent_id.clone()) {
+            Some(executor) => executor,
+            None => return,
+        };
+        assignments.remove(intent_id.clone());
+        env.storage().instance().set(&DataKey::Assignments, &assignments);
+
+        let mut executors = Self::executors(env);
+        if let Some(mut record) = executors.get(executor.clone()) {
+            if let Some(index) = record.active_assignments.first_index_of(intent_id) {
+                record.active_assignments.remove(index);
+            }
+            if completed {
+                record.completed += 1;
+            }
+            Self::lock_bond(env, &mut record);
+            executors.set(executor, record);
+            env.storage().instance().set(&DataKey::Executors, &executors);
+        }
+    }
+
+    fn adjust_bond_total(env: &Env, delta: i128) {
+        let total = Self::get_total_bonded(env.clone()) + delta;
+        env.storage().instance().set(&DataKey::BondTotal, &total);
+    }
+
+    fn lock_bond(env: &Env, record: &mut ExecutorRecord) {
+        let locked_until = env.ledger().timestamp() + Self::get_bond_lock_period(env.clone());
+        record.locked_until = record.locked_until.max(locked_until);
+    }
+
+    fn executors(env: &Env) -> Map<Address, ExecutorRecord> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Executors)
+            .unwrap_or(Map::new(env))
+    }
+
+    fn assignments(env: &Env) -> Map<BytesN<32>, Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Assignments)
+            .unwrap_or(Map::new(env))
+    }
+}
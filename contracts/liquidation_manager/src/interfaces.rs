@@ -0,0 +1,144 @@
+use soroban_sdk::{contractclient, contracttype, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+// Client bindings for the contracts the liquidation manager calls into. The
+// types mirror the callee's #[contracttype] definitions field for field so
+// values decode as-is.
+
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum PositionStatus {
+    Open,
+    InLiquidationCooldown,
+    Closable,
+    Closed,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Position {
+    pub owner: Address,
+    pub principal: i128,
+    pub accrued_interest: i128,
+    pub collateral_ref: BytesN<32>,
+    pub asset: Symbol,
+    pub ltv_bps: i128,
+    pub status: PositionStatus,
+    pub last_oracle_round: i128,
+    pub nonce: i128,
+    pub created_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PositionHealth {
+    pub debt: i128,
+    pub collateral_value: i128,
+    pub ltv_bps: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Attestation {
+    pub custodian: BytesN<32>,
+    pub amount: i128,
+    pub vault_ref: BytesN<32>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct LiquidationOutcome {
+    pub interest_paid: i128,
+    pub principal_paid: i128,
+    pub surplus: i128,
+    pub principal: i128,
+    pub accrued_interest: i128,
+    pub status: PositionStatus,
+}
+
+#[contractclient(name = "LoansClient")]
+pub trait LoansInterface {
+    fn get_position(env: Env, position_id: BytesN<32>) -> Option<Position>;
+
+    fn get_position_health(env: Env, position_id: BytesN<32>) -> PositionHealth;
+
+    fn get_attestation(env: Env, position_id: BytesN<32>) -> Option<Attestation>;
+
+    fn end_cooldown(env: Env, position_id: BytesN<32>) -> bool;
+
+    fn apply_liquidation(
+        env: Env,
+        position_id: BytesN<32>,
+        proceeds: i128,
+        collateral_sold: i128,
+        oracle_round: i128,
+        receipt_nonce: i128,
+    );
+
+    fn preview_liquidation(
+        env: Env,
+        position_id: BytesN<32>,
+        proceeds: i128,
+        collateral_sold: i128
+    ) -> (LiquidationOutcome, PositionHealth);
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Policy {
+    pub max_ltv_bps: i128,
+    pub bands: Vec<i128>,
+    pub slice_pct_bps: i128,
+    pub cooldown_seconds: i128,
+    pub max_slippage_bps: i128,
+    pub staleness_seconds: i128,
+    pub interest_base_bps: i128,
+    pub spread_bps: i128,
+    pub allowed: bool,
+    pub circuit_breaker: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct LiquidationPenalty {
+    pub penalty_bps: i128,
+    pub keeper_bounty_bps: i128,
+}
+
+#[contractclient(name = "PolicyRegistryClient")]
+pub trait PolicyRegistryInterface {
+    fn get_policy(env: Env, asset: Symbol) -> Option<Policy>;
+
+    fn get_version(env: Env) -> i128;
+
+    fn is_venue_allowed(env: Env, venue_hash: Bytes) -> bool;
+
+    fn get_dispute_window(env: Env, asset: Symbol) -> i128;
+
+    fn get_liquidation_penalty(env: Env, asset: Symbol) -> LiquidationPenalty;
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PriceRound {
+    pub price: i128,
+    pub timestamp: u64,
+    pub round_id: i128,
+    pub source: Symbol,
+}
+
+#[contractclient(name = "PriceAdapterClient")]
+pub trait PriceAdapterInterface {
+    fn get_price_if_fresh(env: Env, asset: Symbol, max_age_seconds: u64) -> Option<PriceRound>;
+
+    fn get_round(env: Env, asset: Symbol, round_id: i128) -> Option<PriceRound>;
+}
+
+#[contractclient(name = "InsuranceFundClient")]
+pub trait InsuranceFundInterface {
+    fn deposit_penalty(env: Env, from: Address, amount: i128, position_id: BytesN<32>);
+
+    fn cover_shortfall(env: Env, position_id: BytesN<32>, amount: i128, recipient: Address) -> i128;
+
+    fn get_balance(env: Env) -> i128;
+}
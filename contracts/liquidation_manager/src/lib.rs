@@ -1,19 +1,46 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Env, Symbol, Address, Bytes, Map, BytesN};
+use soroban_sdk::{contract, contractimpl, contracttype, token, vec, Env, Address, Bytes, Map, BytesN, IntoVal, Symbol, Vec};
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+
+mod auction;
+mod disputes;
+mod events;
+mod executors;
+pub mod interfaces;
+
+#[cfg(test)]
+mod test;
+
+pub use auction::{Auction, AuctionClaim, AuctionStatus};
+pub use disputes::{PendingReceipt, ReceiptStatus};
+pub use executors::ExecutorRecord;
+pub use events::*;
+use interfaces::{InsuranceFundClient, LoansClient, Policy, PolicyRegistryClient, Position, PositionHealth, PositionStatus, PriceAdapterClient, PriceRound};
 
 #[contracttype]
 #[derive(Clone, PartialEq)]
 pub enum IntentStatus {
     Open,
+    PartiallyFilled,
     Accepted,
     Cancelled,
+    Expired,
+}
+
+impl IntentStatus {
+    /// Open and partially filled intents still accept receipts
+    pub fn is_live(&self) -> bool {
+        matches!(self, IntentStatus::Open | IntentStatus::PartiallyFilled)
+    }
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub struct LiquidationIntent {
     pub position_id: BytesN<32>,
+    pub asset: Symbol,
     pub notional_to_raise: i128,
+    pub collateral_to_sell: i128,
     pub min_out: i128,
     pub slippage_bps: i128,
     pub deadline: u64,
@@ -22,6 +49,79 @@ pub struct LiquidationIntent {
     pub oracle_round: i128,
     pub status: IntentStatus,
     pub venue_hash: Bytes,
+    pub keeper: Option<Address>,
+    pub proceeds_raised: i128,
+    pub collateral_sold: i128,
+}
+
+/// Settlement parameters for keeper-triggered liquidations. The penalty
+/// itself is per asset and timelocked in the policy registry.
+#[contracttype]
+#[derive(Clone)]
+pub struct LiquidationConfig {
+    pub stablecoin: Address,
+    pub default_venue: Bytes,
+    pub intent_ttl_seconds: u64,
+}
+
+/// Result of `preview_liquidation`: how proceeds would be split and what the
+/// loan would look like after settlement
+#[contracttype]
+#[derive(Clone)]
+pub struct LiquidationPreview {
+    pub penalty: i128,
+    pub reward: i128,
+    pub insurance: i128,
+    pub interest_paid: i128,
+    pub principal_paid: i128,
+    pub surplus: i128,
+    pub shortfall_covered: i128,
+    pub debt_after: i128,
+    pub ltv_bps_after: i128,
+    pub status: PositionStatus,
+}
+
+/// A position that may be liquidated, as checked by `begin_liquidation`
+struct LiquidationTarget {
+    position: Position,
+    policy: Policy,
+    health: PositionHealth,
+    band: u32,
+}
+
+/// Shares of the liquidation penalty charged on some proceeds
+struct PenaltySplit {
+    penalty: i128,
+    reward: i128,
+    insurance: i128,
+}
+
+/// Proceeds of one receipt or auction fill on their way to the loan.
+/// `payer` holds the proceeds and funds `split`; for receipts settled after
+/// a dispute window that is this contract, which escrowed the penalty.
+struct Settlement {
+    proceeds: i128,
+    collateral_sold: i128,
+    oracle_round: i128,
+    submitter: Address,
+    payer: Address,
+    split: PenaltySplit,
+}
+
+/// Terms of a new intent, as given by the admin or derived for a keeper.
+/// `notional_to_raise` is capped at the position's band slice, which is the
+/// whole request when `None`. `expected_oracle_round`, when set, must match
+/// the current round.
+struct IntentParams {
+    position_id: BytesN<32>,
+    notional_to_raise: Option<i128>,
+    slippage_bps: i128,
+    deadline: u64,
+    nonce: i128,
+    policy_version: i128,
+    expected_oracle_round: Option<i128>,
+    venue_hash: Bytes,
+    keeper: Option<Address>,
 }
 
 #[contracttype]
@@ -33,8 +133,30 @@ pub enum DataKey {
     LoansContract,
     Intents,
     Cooldowns,
+    Config,
+    NextNonce,
+    Auctions,
+    AuctionBidders,
+    OpenIntents,
+    PositionIntents(BytesN<32>),
+    Executors,
+    Assignments,
+    MinExecutorBond,
+    InsuranceFund,
+    Guardian,
+    PendingReceipts,
+    BondLockSeconds,
+    PositionAuctions(BytesN<32>),
+    AuctionClaims(BytesN<32>),
+    BondTotal,
+    EscrowTotal,
 }
 
+const BPS: i128 = 10_000;
+
+/// Oracle prices are integers scaled by 1e7
+const PRICE_SCALE: i128 = 10_000_000;
+
 #[contract]
 pub struct LiquidationManager;
 
@@ -56,17 +178,92 @@ impl LiquidationManager {
         env.storage().instance().set(&DataKey::LoansContract, &loans_contract);
     }
 
-    fn require_admin(env: &Env) {
+    fn require_admin(env: &Env) -> Address {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("admin not set");
         admin.require_auth();
+        admin
     }
 
+    fn require_venue_allowed(env: &Env, venue_hash: &Bytes) {
+        if !Self::policy_registry(env).is_venue_allowed(venue_hash) {
+            panic!("venue not allowed");
+        }
+    }
+
+    fn policy_registry(env: &Env) -> PolicyRegistryClient<'_> {
+        let policy_registry: Address = env.storage()
+            .instance()
+            .get(&DataKey::PolicyRegistry)
+            .expect("policy registry not set");
+        PolicyRegistryClient::new(env, &policy_registry)
+    }
+
+    fn loans(env: &Env) -> LoansClient<'_> {
+        let loans_contract: Address = env.storage()
+            .instance()
+            .get(&DataKey::LoansContract)
+            .expect("loans contract not set");
+        LoansClient::new(env, &loans_contract)
+    }
+
+    fn policy(env: &Env, asset: &Symbol) -> Policy {
+        Self::policy_registry(env).get_policy(asset).expect("policy not found")
+    }
+
+    fn price_adapter(env: &Env) -> PriceAdapterClient<'_> {
+        let price_adapter: Address = env.storage()
+            .instance()
+            .get(&DataKey::PriceAdapter)
+            .expect("price adapter not set");
+        PriceAdapterClient::new(env, &price_adapter)
+    }
+
+    fn fresh_price(env: &Env, asset: &Symbol, policy: &Policy) -> PriceRound {
+        Self::price_adapter(env)
+            .get_price_if_fresh(asset, &(policy.staleness_seconds as u64))
+            .expect("price stale")
+    }
+
+    /// Check a receipt's execution round: it must not predate the intent's
+    /// round, must still be within the policy's staleness window, and the
+    /// implied execution price must be within `max_slippage_bps` of it.
+    fn validate_execution_round(
+        env: &Env,
+        intent: &LiquidationIntent,
+        proceeds: i128,
+        collateral_sold: i128,
+        executed_oracle_round: i128
+    ) {
+        if executed_oracle_round < intent.oracle_round {
+            panic!("oracle round older than intent");
+        }
+
+        let policy = Self::policy(env, &intent.asset);
+        let round = Self::price_adapter(env)
+            .get_round(&intent.asset, &executed_oracle_round)
+            .expect("oracle round not found");
+        if env.ledger().timestamp().saturating_sub(round.timestamp) > policy.staleness_seconds as u64 {
+            panic!("oracle round stale");
+        }
+
+        let execution_price = proceeds * PRICE_SCALE / collateral_sold;
+        let deviation = (execution_price - round.price).abs();
+        if deviation * BPS > round.price * policy.max_slippage_bps {
+            panic!("execution price outside slippage");
+        }
+    }
+
+    /// Emit a liquidation intent for a position. The intent is checked against
+    /// the live policy (version, slippage limit, circuit breaker) and priced off
+    /// the current oracle round: `collateral_to_sell` covers `notional_to_raise`
+    /// at the oracle price and `min_out` allows at most `slippage_bps` below it.
+    /// `notional_to_raise` is capped at the slice of collateral value the
+    /// position's band allows, as for keeper intents.
     pub fn emit_intent(
         env: Env,
         intent_id: BytesN<32>,
         position_id: BytesN<32>,
         notional_to_raise: i128,
-        min_out: i128,
         slippage_bps: i128,
         deadline: u64,
         nonce: i128,
@@ -76,82 +273,615 @@ impl LiquidationManager {
     ) {
         Self::require_admin(&env);
 
+        Self::open_intent(
+            &env,
+            &intent_id,
+            IntentParams {
+                position_id,
+                notional_to_raise: Some(notional_to_raise),
+                slippage_bps,
+                deadline,
+                nonce,
+                policy_version,
+                expected_oracle_round: Some(oracle_round),
+                venue_hash,
+                keeper: None,
+            },
+        );
+    }
+
+    /// Set the stablecoin and defaults used for keeper-triggered intents
+    /// (admin only). Penalties are only charged once this is set.
+    pub fn set_liquidation_config(env: Env, config: LiquidationConfig) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Config, &config);
+    }
+
+    pub fn get_liquidation_config(env: Env) -> Option<LiquidationConfig> {
+        env.storage().instance().get(&DataKey::Config)
+    }
+
+    /// Set the insurance fund that receives the non-reward share of penalties
+    /// and covers shortfalls (admin only)
+    pub fn set_insurance_fund(env: Env, insurance_fund: Address) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::InsuranceFund, &insurance_fund);
+    }
+
+    pub fn get_insurance_fund(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::InsuranceFund)
+    }
+
+    /// Permissionless liquidation trigger. Anyone may call this once the
+    /// position's on-chain LTV is above the first policy band (or `max_ltv_bps`
+    /// when no bands are set). The intent raises the band's slice of the
+    /// collateral value at the policy's slippage limit, and `keeper` earns the
+    /// configured share of the liquidation penalty when the receipt settles.
+    pub fn trigger_liquidation(env: Env, position_id: BytesN<32>, keeper: Address) -> BytesN<32> {
+        keeper.require_auth();
+
+        let config = Self::get_liquidation_config(env.clone()).expect("liquidation config not set");
+        let position = Self::loans(&env).get_position(&position_id).expect("position not found");
+        let policy = Self::policy(&env, &position.asset);
+
+        let nonce: i128 = env.storage().instance().get(&DataKey::NextNonce).unwrap_or(1i128);
+        env.storage().instance().set(&DataKey::NextNonce, &(nonce + 1));
+
+        let mut preimage = Bytes::from_array(&env, &position_id.to_array());
+        preimage.extend_from_array(&nonce.to_be_bytes());
+        let intent_id: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+        Self::open_intent(
+            &env,
+            &intent_id,
+            IntentParams {
+                position_id,
+                notional_to_raise: None,
+                slippage_bps: policy.max_slippage_bps,
+                deadline: env.ledger().timestamp() + config.intent_ttl_seconds,
+                nonce,
+                policy_version: Self::policy_registry(&env).get_version(),
+                expected_oracle_round: None,
+                venue_hash: config.default_venue,
+                keeper: Some(keeper),
+            },
+        );
+
+        intent_id
+    }
+
+    /// Liquidation band a position currently sits in (view function), or
+    /// `None` while its on-chain LTV is at or below the first band
+    pub fn get_liquidation_band(env: Env, position_id: BytesN<32>) -> Option<u32> {
+        let loans = Self::loans(&env);
+        let position = loans.get_position(&position_id).expect("position not found");
+        let policy = Self::policy(&env, &position.asset);
+        Self::band_for(&policy, loans.get_position_health(&position_id).ltv_bps)
+    }
+
+    /// Index of the deepest band in `policy.bands` (ascending LTV thresholds in
+    /// bps) that `ltv_bps` exceeds. Without bands, `max_ltv_bps` is the only band.
+    fn band_for(policy: &Policy, ltv_bps: i128) -> Option<u32> {
+        if policy.bands.is_empty() {
+            return if ltv_bps > policy.max_ltv_bps { Some(0) } else { None };
+        }
+
+        let mut band = None;
+        for (index, threshold) in policy.bands.iter().enumerate() {
+            if ltv_bps > threshold {
+                band = Some(index as u32);
+            }
+        }
+        band
+    }
+
+    /// Share of collateral (bps) to sell in `band`: `slice_pct_bps` in the first
+    /// band, growing by another `slice_pct_bps` per deeper band, capped at 100%
+    fn slice_for_band(policy: &Policy, band: u32) -> i128 {
+        (policy.slice_pct_bps * (band as i128 + 1)).min(BPS)
+    }
+
+    /// Checks shared by every way of starting a liquidation (intents and
+    /// auctions): the policy version is current, the circuit breaker is off,
+    /// the position's on-chain LTV is inside a liquidation band, its cooldown
+    /// has passed and it has no live intent or running auction, so concurrent
+    /// liquidations cannot oversell its collateral. A cooled-down loan is
+    /// moved back to Open.
+    fn begin_liquidation(env: &Env, position_id: &BytesN<32>, policy_version: i128) -> LiquidationTarget {
+        if policy_version != Self::policy_registry(env).get_version() {
+            panic!("policy version mismatch");
+        }
+
+        let loans = Self::loans(env);
+        let position = loans.get_position(position_id).expect("position not found");
+        let policy = Self::policy(env, &position.asset);
+        if policy.circuit_breaker {
+            panic!("circuit breaker active");
+        }
+        let health = loans.get_position_health(position_id);
+        let band = Self::band_for(&policy, health.ltv_bps).expect("position healthy");
+        if Self::cooldown_active(env, position_id, &policy) {
+            panic!("position in cooldown");
+        }
+        Self::require_no_active_liquidation(env, position_id);
+        if position.status == PositionStatus::InLiquidationCooldown {
+            // Cooldown is over: move the loan on to its next liquidation
+            loans.end_cooldown(position_id);
+        }
+        LiquidationTarget { position, policy, health, band }
+    }
+
+    /// Panic if a position has a live intent or an auction still taking bids
+    fn require_no_active_liquidation(env: &Env, position_id: &BytesN<32>) {
+        for intent_id in Self::get_position_intents(env.clone(), position_id.clone()).iter() {
+            let intent = Self::get_intent(env.clone(), intent_id).expect("intent not found");
+            if intent.status.is_live() {
+                panic!("position has active intent");
+            }
+        }
+
+        let now = env.ledger().timestamp();
+        for auction_id in Self::get_position_auctions(env.clone(), position_id.clone()).iter() {
+            let auction = Self::get_auction(env.clone(), auction_id).expect("auction not found");
+            if auction.status == AuctionStatus::Active && now <= auction.started_at + auction.duration_seconds {
+                panic!("position has active auction");
+            }
+        }
+    }
+
+    /// Validate and store a new Open intent; shared by every entrypoint that
+    /// creates intents
+    fn open_intent(env: &Env, intent_id: &BytesN<32>, params: IntentParams) -> LiquidationIntent {
+        let IntentParams {
+            position_id,
+            notional_to_raise,
+            slippage_bps,
+            deadline,
+            nonce,
+            policy_version,
+            expected_oracle_round,
+            venue_hash,
+            keeper,
+        } = params;
+        if notional_to_raise.is_some_and(|notional| notional <= 0) {
+            panic!("invalid notional");
+        }
+        Self::require_venue_allowed(env, &venue_hash);
+
+        let LiquidationTarget { position, policy, health, band } = Self::begin_liquidation(env, &position_id, policy_version);
+        let slice = (health.collateral_value * Self::slice_for_band(&policy, band) / BPS).min(health.debt);
+        let notional_to_raise = notional_to_raise.map_or(slice, |notional| notional.min(slice));
+        if slippage_bps < 0 || slippage_bps > policy.max_slippage_bps {
+            panic!("slippage exceeds policy");
+        }
+
+        let round = Self::fresh_price(env, &position.asset, &policy);
+        if round.price <= 0 {
+            panic!("invalid price");
+        }
+        if expected_oracle_round.is_some_and(|expected| expected != round.round_id) {
+            panic!("oracle round mismatch");
+        }
+        let collateral_to_sell = notional_to_raise * PRICE_SCALE / round.price;
+        let min_out = collateral_to_sell * round.price / PRICE_SCALE * (BPS - slippage_bps) / BPS;
+
         let intent = LiquidationIntent {
             position_id: position_id.clone(),
+            asset: position.asset,
             notional_to_raise,
+            collateral_to_sell,
             min_out,
             slippage_bps,
             deadline,
             nonce,
             policy_version,
-            oracle_round,
+            oracle_round: round.round_id,
             status: IntentStatus::Open,
             venue_hash,
+            keeper,
+            proceeds_raised: 0,
+            collateral_sold: 0,
         };
 
         let mut intents: Map<BytesN<32>, LiquidationIntent> = env.storage()
             .instance()
             .get(&DataKey::Intents)
-            .unwrap_or(Map::new(&env));
-        
-        intents.set(intent_id.clone(), intent);
+            .unwrap_or(Map::new(env));
+
+        if intents.contains_key(intent_id.clone()) {
+            panic!("intent already exists");
+        }
+
+        // Nonces are never reused on a position
+        for existing_id in Self::get_position_intents(env.clone(), position_id.clone()).iter() {
+            if intents.get(existing_id).expect("intent not found").nonce == nonce {
+                panic!("duplicate nonce");
+            }
+        }
+
+        intents.set(intent_id.clone(), intent.clone());
         env.storage().instance().set(&DataKey::Intents, &intents);
 
-        env.events().publish(
-            (Symbol::new(&env, "LiquidationIntent"),),
-            (intent_id, position_id, notional_to_raise, min_out)
+        let mut open_intents: Vec<BytesN<32>> = env.storage()
+            .instance()
+            .get(&DataKey::OpenIntents)
+            .unwrap_or(Vec::new(env));
+        open_intents.push_back(intent_id.clone());
+        env.storage().instance().set(&DataKey::OpenIntents, &open_intents);
+
+        let position_key = DataKey::PositionIntents(position_id.clone());
+        let mut position_intents: Vec<BytesN<32>> = env.storage()
+            .persistent()
+            .get(&position_key)
+            .unwrap_or(Vec::new(env));
+        position_intents.push_back(intent_id.clone());
+        env.storage().persistent().set(&position_key, &position_intents);
+
+        events::publish(
+            env,
+            "LiquidationIntent",
+            intent_id.clone(),
+            LiquidationIntentEvent { position_id, notional_to_raise, min_out, deadline, nonce },
         );
+
+        intent
     }
 
+    /// Accept an execution receipt for a live intent. Intents may be filled in
+    /// several chunks: each receipt is applied to the loan as it arrives and
+    /// must clear `min_out` pro rata to the collateral it sold. The intent is
+    /// Accepted once `notional_to_raise` is reached and PartiallyFilled before
+    /// that. Assigned intents take receipts from their executor only;
+    /// unassigned ones from the admin. When the asset has a dispute window in
+    /// the policy registry the penalty is escrowed from the submitter and the
+    /// receipt is held as Pending instead, to be applied by `finalize_receipt`
+    /// once the window passes unchallenged.
     pub fn accept_receipt(
         env: Env,
         intent_id: BytesN<32>,
         proceeds: i128,
+        collateral_sold: i128,
         executed_oracle_round: i128
     ) {
-        Self::require_admin(&env);
+        let submitter = match Self::get_assigned_executor(env.clone(), intent_id.clone()) {
+            Some(executor) => {
+                executor.require_auth();
+                executor
+            }
+            None => Self::require_admin(&env),
+        };
 
-        let mut intents: Map<BytesN<32>, LiquidationIntent> = env.storage()
-            .instance()
-            .get(&DataKey::Intents)
-            .unwrap_or(Map::new(&env));
-        
-        let mut intent = intents.get(intent_id.clone()).expect("intent not found");
+        let intent = Self::get_intent(env.clone(), intent_id.clone()).expect("intent not found");
 
-        if intent.status != IntentStatus::Open {
+        if !intent.status.is_live() {
             panic!("intent not open");
         }
+        if Self::has_pending_receipt(&env, &intent_id) {
+            panic!("receipt pending");
+        }
 
         let now = env.ledger().timestamp();
         if now > intent.deadline {
             panic!("intent expired");
         }
 
-        if proceeds < intent.min_out {
+        if proceeds <= 0 || collateral_sold <= 0 {
+            panic!("invalid receipt");
+        }
+        if intent.collateral_sold + collateral_sold > intent.collateral_to_sell {
+            panic!("collateral oversold");
+        }
+        if proceeds * intent.collateral_to_sell < intent.min_out * collateral_sold {
             panic!("proceeds below min_out");
         }
 
-        intent.status = IntentStatus::Accepted;
+        Self::validate_execution_round(&env, &intent, proceeds, collateral_sold, executed_oracle_round);
+
+        // The venue may have been delisted since the intent was emitted
+        Self::require_venue_allowed(&env, &intent.venue_hash);
+
+        let split = Self::penalty_split(&env, &intent.asset, proceeds);
+        let dispute_window = Self::policy_registry(&env).get_dispute_window(&intent.asset);
+        if dispute_window > 0 {
+            if split.penalty > 0 {
+                let config = Self::get_liquidation_config(env.clone()).expect("liquidation config not set");
+                token::Client::new(&env, &config.stablecoin).transfer(&submitter, &env.current_contract_address(), &split.penalty);
+                Self::adjust_escrow(&env, split.penalty);
+            }
+            let receipt = PendingReceipt {
+                proceeds,
+                collateral_sold,
+                executed_oracle_round,
+                submitter,
+                submitted_at: now,
+                challenge_deadline: now + dispute_window as u64,
+                status: ReceiptStatus::Pending,
+                challenger: None,
+                penalty: split.penalty,
+                reward: split.reward,
+                insurance: split.insurance,
+            };
+            Self::hold_receipt(&env, &intent_id, &intent.position_id, receipt);
+            return;
+        }
+
+        let settlement = Settlement {
+            proceeds,
+            collateral_sold,
+            oracle_round: executed_oracle_round,
+            submitter: submitter.clone(),
+            payer: submitter,
+            split,
+        };
+        Self::apply_receipt(&env, &intent_id, intent, settlement);
+    }
+
+    /// Record a validated receipt against its intent, settle it against the
+    /// loan and start the position's cooldown
+    fn apply_receipt(env: &Env, intent_id: &BytesN<32>, mut intent: LiquidationIntent, settlement: Settlement) {
+        let proceeds = settlement.proceeds;
+        let executed_oracle_round = settlement.oracle_round;
+        intent.proceeds_raised += proceeds;
+        intent.collateral_sold += settlement.collateral_sold;
+        let filled = intent.proceeds_raised >= intent.notional_to_raise
+            || intent.collateral_sold == intent.collateral_to_sell;
+        intent.status = if filled { IntentStatus::Accepted } else { IntentStatus::PartiallyFilled };
+
+        let mut intents: Map<BytesN<32>, LiquidationIntent> = env.storage()
+            .instance()
+            .get(&DataKey::Intents)
+            .unwrap_or(Map::new(env));
         intents.set(intent_id.clone(), intent.clone());
         env.storage().instance().set(&DataKey::Intents, &intents);
+        if filled {
+            Self::remove_open_intent(env, intent_id);
+            Self::release_assignment(env, intent_id, true);
+        }
+
+        // Settle against the loan in the same transaction. Without a keeper the
+        // submitting executor keeps the reward share of the penalty.
+        let reward_to = intent.keeper.clone().or(Some(settlement.submitter.clone()));
+        Self::settle(env, &intent.position_id, intent.nonce, &settlement, reward_to);
+        Self::cover_shortfall(env, &intent.position_id, executed_oracle_round, intent.nonce);
+
+        events::publish(
+            env,
+            "ReceiptAccepted",
+            intent_id.clone(),
+            ReceiptAcceptedEvent {
+                position_id: intent.position_id.clone(),
+                proceeds,
+                oracle_round: executed_oracle_round,
+            },
+        );
+        if filled {
+            events::publish(
+                env,
+                "IntentFilled",
+                intent_id.clone(),
+                IntentFilledEvent {
+                    position_id: intent.position_id.clone(),
+                    proceeds_raised: intent.proceeds_raised,
+                    collateral_sold: intent.collateral_sold,
+                },
+            );
+        }
+
+        Self::start_cooldown(env, &intent.position_id);
+    }
+
+    /// Apply settled proceeds to the loan, withholding the penalty in
+    /// `settlement.split`. The payer funds the split: the reward share goes to
+    /// `reward_to` and the rest to the insurance fund. Returns the amount the
+    /// payer transferred out.
+    fn settle(
+        env: &Env,
+        position_id: &BytesN<32>,
+        nonce: i128,
+        settlement: &Settlement,
+        reward_to: Option<Address>
+    ) -> i128 {
+        let split = &settlement.split;
+        let payer = &settlement.payer;
+        let proceeds = settlement.proceeds;
+        let mut paid = 0;
+        if split.penalty > 0 {
+            let config = Self::get_liquidation_config(env.clone()).expect("liquidation config not set");
+            if let Some(recipient) = reward_to.clone() {
+                if split.reward > 0 && recipient != *payer {
+                    token::Client::new(env, &config.stablecoin).transfer(payer, &recipient, &split.reward);
+                    paid += split.reward;
+                }
+            }
+
+            if split.insurance > 0 {
+                let insurance_fund = Self::get_insurance_fund(env.clone()).expect("insurance fund not set");
+                if *payer == env.current_contract_address() {
+                    // The fund pulls escrowed stablecoin from this contract
+                    env.authorize_as_current_contract(vec![
+                        env,
+                        InvokerContractAuthEntry::Contract(SubContractInvocation {
+                            context: ContractContext {
+                                contract: config.stablecoin.clone(),
+                                fn_name: Symbol::new(env, "transfer"),
+                                args: (payer.clone(), insurance_fund.clone(), split.insurance).into_val(env),
+                            },
+                            sub_invocations: vec![env],
+                        }),
+                    ]);
+                }
+                InsuranceFundClient::new(env, &insurance_fund).deposit_penalty(payer, &split.insurance, position_id);
+                paid += split.insurance;
+            }
+
+            events::publish(
+                env,
+                "PenaltyCharged",
+                position_id.clone(),
+                PenaltyChargedEvent {
+                    proceeds,
+                    penalty: split.penalty,
+                    reward: split.reward,
+                    reward_to,
+                    insurance: split.insurance,
+                },
+            );
+        }
+
+        Self::loans(env).apply_liquidation(
+            position_id,
+            &(proceeds - split.penalty),
+            &settlement.collateral_sold,
+            &settlement.oracle_round,
+            &nonce,
+        );
+        paid
+    }
+
+    /// Penalty on `proceeds` from selling `asset` and its split into the
+    /// reward share and the insurance share, using the asset's liquidation
+    /// penalty in the policy registry. `keeper_bounty_bps` of the penalty is
+    /// paid to the keeper, or kept by the executor when no keeper triggered
+    /// the intent. Without an insurance fund only the reward share is
+    /// charged, so the loan is credited everything else.
+    fn penalty_split(env: &Env, asset: &Symbol, proceeds: i128) -> PenaltySplit {
+        if Self::get_liquidation_config(env.clone()).is_none() {
+            return PenaltySplit { penalty: 0, reward: 0, insurance: 0 };
+        }
+
+        let rates = Self::policy_registry(env).get_liquidation_penalty(asset);
+        let mut penalty = proceeds * rates.penalty_bps / BPS;
+        let reward = penalty * rates.keeper_bounty_bps / BPS;
+        if Self::get_insurance_fund(env.clone()).is_none() {
+            penalty = reward;
+        }
+        PenaltySplit { penalty, reward, insurance: penalty - reward }
+    }
+
+    /// Smallest proceeds from selling `asset` that still leave `net` once
+    /// `penalty_split` has withheld the penalty
+    fn gross_for_net(env: &Env, asset: &Symbol, net: i128) -> i128 {
+        if Self::get_liquidation_config(env.clone()).is_none() {
+            return net;
+        }
+
+        // Withheld share of the proceeds, in bps of bps
+        let rates = Self::policy_registry(env).get_liquidation_penalty(asset);
+        let withheld = if Self::get_insurance_fund(env.clone()).is_some() {
+            rates.penalty_bps * BPS
+        } else {
+            rates.penalty_bps * rates.keeper_bounty_bps
+        };
+        let kept = BPS * BPS - withheld;
+        (net * BPS * BPS + kept - 1) / kept
+    }
+
+    /// Simulate settling a receipt for `proceeds` and `collateral_sold`
+    /// without changing any state. Uses the same penalty split as settlement,
+    /// the Loans contract's own liquidation arithmetic and, when the sale
+    /// would leave no attested collateral, the insurance fund's shortfall cover.
+    pub fn preview_liquidation(
+        env: Env,
+        position_id: BytesN<32>,
+        proceeds: i128,
+        collateral_sold: i128
+    ) -> LiquidationPreview {
+        if proceeds <= 0 || collateral_sold < 0 {
+            panic!("invalid amount");
+        }
+
+        let loans = Self::loans(&env);
+        let position = loans.get_position(&position_id).expect("position not found");
+        let split = Self::penalty_split(&env, &position.asset, proceeds);
+        let credited = proceeds - split.penalty;
+        let (mut outcome, mut health) = loans.preview_liquidation(&position_id, &credited, &collateral_sold);
 
-        // Set cooldown for position
+        // Mirrors cover_shortfall, which runs after the penalty's insurance
+        // share has been deposited and the sale netted off the attestation
+        let mut shortfall_covered = 0;
+        if let Some(insurance_fund) = Self::get_insurance_fund(env.clone()) {
+            let sold_out = loans.get_attestation(&position_id)
+                .is_some_and(|attestation| attestation.amount <= collateral_sold);
+            if sold_out && health.debt > 0 {
+                let balance = InsuranceFundClient::new(&env, &insurance_fund).get_balance();
+                shortfall_covered = health.debt.min(balance + split.insurance);
+            }
+        }
+        if shortfall_covered > 0 {
+            (outcome, health) = loans.preview_liquidation(&position_id, &(credited + shortfall_covered), &collateral_sold);
+        }
+
+        LiquidationPreview {
+            penalty: split.penalty,
+            reward: split.reward,
+            insurance: split.insurance,
+            interest_paid: outcome.interest_paid,
+            principal_paid: outcome.principal_paid,
+            surplus: outcome.surplus,
+            shortfall_covered,
+            debt_after: health.debt,
+            ltv_bps_after: health.ltv_bps,
+            status: outcome.status,
+        }
+    }
+
+    /// Draw on the insurance fund for debt left on a position once
+    /// liquidations have sold all of its attested collateral (Loans nets each
+    /// sale off the attestation). The payout goes to the admin treasury and is
+    /// applied to the loan without a penalty.
+    fn cover_shortfall(env: &Env, position_id: &BytesN<32>, oracle_round: i128, nonce: i128) {
+        let insurance_fund = match Self::get_insurance_fund(env.clone()) {
+            Some(insurance_fund) => insurance_fund,
+            None => return,
+        };
+
+        // Without an attestation there is nothing to measure the sales against
+        let loans = Self::loans(env);
+        let attestation = match loans.get_attestation(position_id) {
+            Some(attestation) => attestation,
+            None => return,
+        };
+        if attestation.amount > 0 {
+            return;
+        }
+        let position = match loans.get_position(position_id) {
+            Some(position) => position,
+            None => return,
+        };
+        let shortfall = position.principal + position.accrued_interest;
+        if shortfall <= 0 {
+            return;
+        }
+
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("admin not set");
+        let covered = InsuranceFundClient::new(env, &insurance_fund).cover_shortfall(position_id, &shortfall, &admin);
+        if covered > 0 {
+            loans.apply_liquidation(position_id, &covered, &0, &oracle_round, &nonce);
+        }
+
+        events::publish(
+            env,
+            "ShortfallCovered",
+            position_id.clone(),
+            ShortfallCoveredEvent { shortfall, covered },
+        );
+    }
+
+    fn start_cooldown(env: &Env, position_id: &BytesN<32>) {
+        let now = env.ledger().timestamp();
         let mut cooldowns: Map<BytesN<32>, u64> = env.storage()
             .instance()
             .get(&DataKey::Cooldowns)
-            .unwrap_or(Map::new(&env));
-        
-        cooldowns.set(intent.position_id.clone(), now);
+            .unwrap_or(Map::new(env));
+
+        cooldowns.set(position_id.clone(), now);
         env.storage().instance().set(&DataKey::Cooldowns, &cooldowns);
 
-        env.events().publish(
-            (Symbol::new(&env, "ReceiptAccepted"),),
-            (intent_id, proceeds, executed_oracle_round)
-        );
-        
-        env.events().publish(
-            (Symbol::new(&env, "CooldownStarted"),),
-            (intent.position_id, now)
+        events::publish(
+            env,
+            "CooldownStarted",
+            position_id.clone(),
+            CooldownStartedEvent { started_at: now },
         );
     }
 
@@ -164,11 +894,102 @@ impl LiquidationManager {
             .unwrap_or(Map::new(&env));
         
         let mut intent = intents.get(intent_id.clone()).expect("intent not found");
+        if !intent.status.is_live() {
+            panic!("intent not open");
+        }
+        if Self::has_pending_receipt(&env, &intent_id) {
+            panic!("receipt pending");
+        }
         intent.status = IntentStatus::Cancelled;
-        intents.set(intent_id.clone(), intent);
+        intents.set(intent_id.clone(), intent.clone());
         env.storage().instance().set(&DataKey::Intents, &intents);
+        Self::remove_open_intent(&env, &intent_id);
+        Self::release_assignment(&env, &intent_id, false);
 
-        env.events().publish((Symbol::new(&env, "IntentCancelled"),), intent_id);
+        events::publish(
+            &env,
+            "IntentCancelled",
+            intent_id,
+            IntentCancelledEvent { position_id: intent.position_id },
+        );
+    }
+
+    /// Mark up to `limit` live intents whose deadline has passed as Expired,
+    /// oldest first, skipping intents with a receipt still under dispute.
+    /// Anyone may call this. Returns the number expired.
+    pub fn expire_intents(env: Env, limit: u32) -> u32 {
+        let now = env.ledger().timestamp();
+
+        let mut intents: Map<BytesN<32>, LiquidationIntent> = env.storage()
+            .instance()
+            .get(&DataKey::Intents)
+            .unwrap_or(Map::new(&env));
+        let open_intents: Vec<BytesN<32>> = env.storage()
+            .instance()
+            .get(&DataKey::OpenIntents)
+            .unwrap_or(Vec::new(&env));
+
+        let mut still_open = Vec::new(&env);
+        let mut expired = 0u32;
+        for intent_id in open_intents.iter() {
+            let mut intent = intents.get(intent_id.clone()).expect("intent not found");
+            if expired < limit && now > intent.deadline && !Self::has_pending_receipt(&env, &intent_id) {
+                intent.status = IntentStatus::Expired;
+                intents.set(intent_id.clone(), intent.clone());
+                Self::release_assignment(&env, &intent_id, false);
+                expired += 1;
+
+                events::publish(
+                    &env,
+                    "IntentExpired",
+                    intent_id,
+                    IntentExpiredEvent { position_id: intent.position_id },
+                );
+            } else {
+                still_open.push_back(intent_id);
+            }
+        }
+
+        if expired > 0 {
+            env.storage().instance().set(&DataKey::Intents, &intents);
+            env.storage().instance().set(&DataKey::OpenIntents, &still_open);
+        }
+        expired
+    }
+
+    /// Page through live (Open or PartiallyFilled) intents in creation order.
+    /// `cursor` is an offset into the open list, which shrinks as intents are
+    /// filled, cancelled or expired.
+    pub fn list_open_intents(env: Env, cursor: u32, limit: u32) -> Vec<BytesN<32>> {
+        let open_intents: Vec<BytesN<32>> = env.storage()
+            .instance()
+            .get(&DataKey::OpenIntents)
+            .unwrap_or(Vec::new(&env));
+
+        if cursor >= open_intents.len() {
+            return Vec::new(&env);
+        }
+        let end = cursor.saturating_add(limit).min(open_intents.len());
+        open_intents.slice(cursor..end)
+    }
+
+    /// Every intent ever emitted for a position, oldest first
+    pub fn get_position_intents(env: Env, position_id: BytesN<32>) -> Vec<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PositionIntents(position_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    fn remove_open_intent(env: &Env, intent_id: &BytesN<32>) {
+        let mut open_intents: Vec<BytesN<32>> = env.storage()
+            .instance()
+            .get(&DataKey::OpenIntents)
+            .unwrap_or(Vec::new(env));
+        if let Some(index) = open_intents.first_index_of(intent_id) {
+            open_intents.remove(index);
+            env.storage().instance().set(&DataKey::OpenIntents, &open_intents);
+        }
     }
 
     pub fn get_intent(env: Env, intent_id: BytesN<32>) -> Option<LiquidationIntent> {
@@ -187,10 +1008,18 @@ impl LiquidationManager {
         cooldowns.get(position_id)
     }
 
-    pub fn is_in_cooldown(env: Env, position_id: BytesN<32>, cooldown_seconds: u64) -> bool {
-        if let Some(cooldown_start) = Self::get_cooldown(env.clone(), position_id) {
+    /// Whether a position is still inside the `Policy.cooldown_seconds` window
+    /// of its asset since its last liquidation
+    pub fn is_in_cooldown(env: Env, position_id: BytesN<32>) -> bool {
+        let position = Self::loans(&env).get_position(&position_id).expect("position not found");
+        let policy = Self::policy(&env, &position.asset);
+        Self::cooldown_active(&env, &position_id, &policy)
+    }
+
+    fn cooldown_active(env: &Env, position_id: &BytesN<32>, policy: &Policy) -> bool {
+        if let Some(cooldown_start) = Self::get_cooldown(env.clone(), position_id.clone()) {
             let now = env.ledger().timestamp();
-            return now < cooldown_start + cooldown_seconds;
+            return now < cooldown_start + policy.cooldown_seconds as u64;
         }
         false
     }
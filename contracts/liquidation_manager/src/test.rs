@@ -0,0 +1,1018 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use loans::{Loans, LoansClient as LoansContractClient};
+use policy_registry::{PolicyRegistry, PolicyRegistryClient as RegistryClient};
+use insurance_fund::{InsuranceFund, InsuranceFundClient as FundContractClient};
+use price_adapter::{PriceAdapter, PriceAdapterClient as AdapterClient};
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{token::StellarAssetClient, xdr::ToXdr, Val};
+
+/// 2 stablecoin per unit of collateral, scaled by PRICE_SCALE
+const PRICE: i128 = 2 * PRICE_SCALE;
+
+/// Collateral attested to the test position, worth 200,000 at `PRICE`
+const COLLATERAL: i128 = 100_000;
+
+/// Debt of the test position: 5,500 bps LTV, inside the first policy band
+const DEBT: i128 = 110_000;
+
+struct Setup {
+    env: Env,
+    manager: LiquidationManagerClient<'static>,
+    loans: LoansContractClient<'static>,
+    registry: RegistryClient<'static>,
+    adapter: AdapterClient<'static>,
+    stablecoin: Address,
+    admin: Address,
+    owner: Address,
+    venue: Bytes,
+    asset: Symbol,
+    custodian: SigningKey,
+}
+
+/// Loans, PolicyRegistry, PriceAdapter, a stablecoin and the manager, all
+/// deployed and wired together
+fn setup() -> Setup {
+    setup_with_dispute_window(0)
+}
+
+/// `setup` with receipts held for `dispute_window_seconds` before settling
+fn setup_with_dispute_window(dispute_window_seconds: i128) -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    // Registry changes queued below take effect at 1,000,000
+    env.ledger().set_timestamp(1_000_000 - 24 * 60 * 60);
+
+    let admin = Address::generate(&env);
+    let asset = Symbol::new(&env, "BTC");
+    let venue = Bytes::from_slice(&env, b"venue");
+
+    let registry = RegistryClient::new(&env, &env.register(PolicyRegistry, ()));
+    registry.initialize(&admin);
+    registry.set_policy(
+        &asset,
+        &7_000,
+        &vec![&env, 5_000i128, 6_000, 6_500],
+        &2_500,
+        &3_600,
+        &100,
+        &600,
+        &500,
+        &200,
+        &true,
+    );
+    let mut proposals = std::vec![
+        registry.queue_venue(&venue),
+        registry.queue_liquidation_penalty(&asset, &policy_registry::LiquidationPenalty {
+            penalty_bps: 500,
+            keeper_bounty_bps: 5_000,
+        }),
+    ];
+    if dispute_window_seconds > 0 {
+        proposals.push(registry.queue_dispute_window(&asset, &dispute_window_seconds));
+    }
+    env.ledger().set_timestamp(1_000_000);
+    for proposal_id in proposals {
+        registry.execute_policy(&proposal_id);
+    }
+
+    let adapter = AdapterClient::new(&env, &env.register(PriceAdapter, ()));
+    adapter.initialize(&admin, &10_000);
+    adapter.update_price(&asset, &PRICE, &1, &Symbol::new(&env, "feed"));
+
+    let manager = LiquidationManagerClient::new(&env, &env.register(LiquidationManager, ()));
+    let loans = LoansContractClient::new(&env, &env.register(Loans, ()));
+    loans.initialize(&admin, &registry.address, &adapter.address, &manager.address);
+    manager.initialize(&admin, &registry.address, &adapter.address, &loans.address);
+
+    let stablecoin = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    manager.set_liquidation_config(&LiquidationConfig {
+        stablecoin: stablecoin.clone(),
+        default_venue: venue.clone(),
+        intent_ttl_seconds: 3_600,
+    });
+
+    let custodian = SigningKey::from_bytes(&[7u8; 32]);
+    loans.add_custodian(&BytesN::from_array(&env, &custodian.verifying_key().to_bytes()));
+
+    let owner = Address::generate(&env);
+    Setup { env, manager, loans, registry, adapter, stablecoin, admin, owner, venue, asset, custodian }
+}
+
+fn id(env: &Env, n: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[n; 32])
+}
+
+/// Open a position against `COLLATERAL` attested units and borrow `debt`
+fn open_position(s: &Setup, position_id: &BytesN<32>, debt: i128) {
+    let vault_ref = id(&s.env, 100);
+    s.loans.open_position(position_id, &s.owner, &vault_ref, &s.asset);
+
+    let timestamp = s.env.ledger().timestamp();
+    let mut message = Bytes::from_slice(&s.env, b"legasi:attest:v2");
+    message.append(&s.loans.address.clone().to_xdr(&s.env));
+    message.append(&position_id.clone().into());
+    message.append(&vault_ref.clone().into());
+    message.extend_from_array(&COLLATERAL.to_be_bytes());
+    message.extend_from_array(&timestamp.to_be_bytes());
+    let mut buf = std::vec![0u8; message.len() as usize];
+    message.copy_into_slice(&mut buf);
+    let signature = BytesN::from_array(&s.env, &s.custodian.sign(&buf).to_bytes());
+
+    let custodian = BytesN::from_array(&s.env, &s.custodian.verifying_key().to_bytes());
+    s.loans.attest_collateral(position_id, &custodian, &COLLATERAL, &vault_ref, &timestamp, &signature);
+    s.loans.draw(position_id, &debt, &1);
+}
+
+/// Admin intent raising `notional` for a position at the current round
+fn emit_intent(s: &Setup, intent_id: &BytesN<32>, position_id: &BytesN<32>, notional: i128, nonce: i128) {
+    s.manager.emit_intent(
+        intent_id,
+        position_id,
+        &notional,
+        &100,
+        &(s.env.ledger().timestamp() + 600),
+        &nonce,
+        &s.registry.get_version(),
+        &1,
+        &s.venue,
+    );
+}
+
+fn debt(s: &Setup, position_id: &BytesN<32>) -> i128 {
+    s.loans.get_total_debt(position_id)
+}
+
+#[test]
+fn accept_receipt_reduces_debt_in_the_same_transaction() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 20_000, 1);
+
+    // Sells 10,000 units at the oracle price. The 5% penalty is 1,000; with
+    // no insurance fund only the 500 reward share is withheld.
+    s.manager.accept_receipt(&intent_id, &20_000, &10_000, &1);
+
+    assert_eq!(debt(&s, &position_id), DEBT - 19_500);
+    assert_eq!(s.loans.get_attestation(&position_id).unwrap().amount, COLLATERAL - 10_000);
+    let position = s.loans.get_position(&position_id).unwrap();
+    assert!(position.status == loans::PositionStatus::InLiquidationCooldown);
+    assert_eq!(position.nonce, 1);
+
+    let history = s.loans.get_position_history(&position_id, &0, &10);
+    let last = history.last().unwrap();
+    assert!(last.action == loans::HistoryAction::Liquidation);
+    assert_eq!(last.amount, 19_500);
+    assert_eq!(last.actor, s.manager.address);
+
+    let intent = s.manager.get_intent(&intent_id).unwrap();
+    assert!(intent.status == IntentStatus::Accepted);
+    assert_eq!(intent.proceeds_raised, 20_000);
+}
+
+#[test]
+fn partial_receipts_each_reduce_debt() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 20_000, 1);
+
+    s.manager.accept_receipt(&intent_id, &8_000, &4_000, &1);
+    assert_eq!(debt(&s, &position_id), DEBT - 7_800);
+    assert!(s.manager.get_intent(&intent_id).unwrap().status == IntentStatus::PartiallyFilled);
+
+    s.manager.accept_receipt(&intent_id, &12_000, &6_000, &1);
+    assert_eq!(debt(&s, &position_id), DEBT - 7_800 - 11_700);
+    assert!(s.manager.get_intent(&intent_id).unwrap().status == IntentStatus::Accepted);
+}
+
+#[test]
+fn loans_rejects_liquidations_not_sent_by_the_manager() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, 100_000);
+
+    // Only the configured manager may apply proceeds; a direct call without
+    // its authorization fails and leaves the debt untouched
+    s.env.mock_auths(&[]);
+    assert!(s.loans.try_apply_liquidation(&position_id, &50_000, &0, &1, &1).is_err());
+    assert_eq!(debt(&s, &position_id), 100_000);
+}
+
+#[test]
+#[should_panic(expected = "execution price outside slippage")]
+fn receipt_off_the_oracle_price_is_rejected() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 20_000, 1);
+
+    // 20,000 for 9,000 units is 11% above the oracle price
+    s.manager.accept_receipt(&intent_id, &20_000, &9_000, &1);
+}
+
+/// Assert that `preview`, taken before a receipt settled, matches the
+/// position as the settlement left it
+fn assert_preview_matches(s: &Setup, position_id: &BytesN<32>, preview: &LiquidationPreview, debt_before: i128) {
+    // Read through the manager's own client so the status types line up
+    let position = LoansClient::new(&s.env, &s.loans.address).get_position(position_id).unwrap();
+    let health = s.loans.get_position_health(position_id);
+    assert_eq!(preview.debt_after, health.debt);
+    assert_eq!(preview.ltv_bps_after, health.ltv_bps);
+    assert!(preview.status == position.status);
+    assert_eq!(preview.interest_paid + preview.principal_paid, debt_before - health.debt);
+}
+
+#[test]
+fn preview_matches_accept_receipt() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 20_000, 1);
+
+    let preview = s.manager.preview_liquidation(&position_id, &20_000, &10_000);
+    assert_eq!(preview.penalty, 500);
+    assert_eq!(preview.shortfall_covered, 0);
+    // 110,000 - 19,500 against the 90,000 units left, worth 180,000
+    assert_eq!(preview.ltv_bps_after, 5_027);
+
+    s.manager.accept_receipt(&intent_id, &20_000, &10_000, &1);
+    assert_preview_matches(&s, &position_id, &preview, DEBT);
+}
+
+/// Position at 11,000 bps LTV after the price halves, with an intent selling
+/// all of its collateral and an insurance fund holding 10,000
+fn sold_out_intent(s: &Setup) -> (BytesN<32>, BytesN<32>, FundContractClient<'static>) {
+    let position_id = id(&s.env, 1);
+    open_position(s, &position_id, DEBT);
+
+    let fund = FundContractClient::new(&s.env, &s.env.register(InsuranceFund, ()));
+    fund.initialize(&s.admin, &s.stablecoin, &s.manager.address);
+    s.manager.set_insurance_fund(&fund.address);
+    let depositor = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.stablecoin).mint(&depositor, &10_000);
+    fund.deposit(&depositor, &10_000);
+    StellarAssetClient::new(&s.env, &s.stablecoin).mint(&s.admin, &2_500);
+
+    // Allow a single intent to sell everything, then halve the price so the
+    // collateral no longer covers the debt
+    let policy = policy_registry::Policy { slice_pct_bps: 10_000, ..s.registry.get_policy(&s.asset).unwrap() };
+    let proposal_id = s.registry.queue_policy(&s.asset, &policy);
+    s.env.ledger().set_timestamp(1_000_000 + 24 * 60 * 60);
+    s.registry.execute_policy(&proposal_id);
+    s.adapter.update_price(&s.asset, &PRICE_SCALE, &2, &Symbol::new(&s.env, "feed"));
+
+    let intent_id = id(&s.env, 2);
+    s.manager.emit_intent(
+        &intent_id,
+        &position_id,
+        &COLLATERAL,
+        &100,
+        &(s.env.ledger().timestamp() + 600),
+        &1,
+        &s.registry.get_version(),
+        &2,
+        &s.venue,
+    );
+    (position_id, intent_id, fund)
+}
+
+#[test]
+fn preview_includes_the_shortfall_the_fund_covers() {
+    let s = setup();
+    let (position_id, intent_id, fund) = sold_out_intent(&s);
+
+    // The 5,000 penalty leaves 15,000 of debt once all collateral is sold;
+    // the fund holds 10,000 plus the 2,500 insurance share of the penalty
+    let preview = s.manager.preview_liquidation(&position_id, &COLLATERAL, &COLLATERAL);
+    assert_eq!(preview.penalty, 5_000);
+    assert_eq!(preview.insurance, 2_500);
+    assert_eq!(preview.shortfall_covered, 12_500);
+    assert_eq!(preview.debt_after, 2_500);
+    assert_eq!(preview.ltv_bps_after, i128::MAX);
+
+    s.manager.accept_receipt(&intent_id, &COLLATERAL, &COLLATERAL, &2);
+    assert_preview_matches(&s, &position_id, &preview, DEBT);
+    assert_eq!(fund.get_balance(), 0);
+}
+
+/// The event `name` as the manager publishes it
+fn event<I, D>(s: &Setup, name: &str, id: I, data: D) -> (Address, Vec<Val>, Val)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    (
+        s.manager.address.clone(),
+        (Symbol::new(&s.env, "liquidation_manager"), Symbol::new(&s.env, name), EVENT_VERSION, id).into_val(&s.env),
+        data.into_val(&s.env),
+    )
+}
+
+/// Events the manager published in the last call, leaving out those of the
+/// token, Loans and insurance fund contracts it called
+fn manager_events(s: &Setup) -> Vec<(Address, Vec<Val>, Val)> {
+    let mut events = Vec::new(&s.env);
+    for event in s.env.events().all().iter() {
+        if event.0 == s.manager.address {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
+#[test]
+fn emit_intent_emits_liquidation_intent() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 20_000, 1);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "LiquidationIntent", intent_id, LiquidationIntentEvent {
+                position_id,
+                notional_to_raise: 20_000,
+                min_out: 19_800,
+                deadline: 1_000_600,
+                nonce: 1,
+            })
+        ]
+    );
+}
+
+#[test]
+fn accept_receipt_emits_settlement_events() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 20_000, 1);
+
+    s.manager.accept_receipt(&intent_id, &20_000, &10_000, &1);
+
+    // The penalty is charged before Loans is credited, all in one call
+    let debt_reduced = (
+        s.loans.address.clone(),
+        (Symbol::new(&s.env, "loans"), Symbol::new(&s.env, "DebtReduced"), loans::EVENT_VERSION, position_id.clone())
+            .into_val(&s.env),
+        loans::DebtReducedEvent { proceeds: 19_500, oracle_round: 1, receipt_nonce: 1 }.into_val(&s.env),
+    );
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "PenaltyCharged", position_id.clone(), PenaltyChargedEvent {
+                proceeds: 20_000,
+                penalty: 500,
+                reward: 500,
+                reward_to: Some(s.admin.clone()),
+                insurance: 0,
+            }),
+            debt_reduced,
+            event(&s, "ReceiptAccepted", intent_id.clone(), ReceiptAcceptedEvent {
+                position_id: position_id.clone(),
+                proceeds: 20_000,
+                oracle_round: 1,
+            }),
+            event(&s, "IntentFilled", intent_id, IntentFilledEvent {
+                position_id: position_id.clone(),
+                proceeds_raised: 20_000,
+                collateral_sold: 10_000,
+            }),
+            event(&s, "CooldownStarted", position_id, CooldownStartedEvent { started_at: 1_000_000 })
+        ]
+    );
+}
+
+#[test]
+fn closing_intents_emit_their_events() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+
+    let cancelled = id(&s.env, 2);
+    emit_intent(&s, &cancelled, &position_id, 20_000, 1);
+    s.manager.cancel_intent(&cancelled);
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "IntentCancelled", cancelled, IntentCancelledEvent { position_id: position_id.clone() })]
+    );
+
+    let expired = id(&s.env, 3);
+    emit_intent(&s, &expired, &position_id, 20_000, 2);
+    s.env.ledger().set_timestamp(1_000_601);
+    s.manager.expire_intents(&10);
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "IntentExpired", expired, IntentExpiredEvent { position_id })]
+    );
+}
+
+/// Position debt at the edges of the policy bands (5000/6000/6500 bps) on
+/// 200,000 of collateral, with the band and keeper notional each one gives
+const BAND_EDGES: [(i128, Option<u32>, i128); 6] = [
+    (100_000, None, 0),
+    (100_020, Some(0), 50_000),
+    (120_000, Some(0), 50_000),
+    (120_020, Some(1), 100_000),
+    (130_000, Some(1), 100_000),
+    // The deepest slice would raise 150,000 but is capped at the debt
+    (130_020, Some(2), 130_020),
+];
+
+#[test]
+fn band_follows_ltv_across_every_threshold() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, BAND_EDGES[0].0);
+    assert_eq!(s.manager.get_liquidation_band(&position_id), None);
+
+    // Interest moves the same position through each band in turn
+    for window in BAND_EDGES.windows(2) {
+        let (from, _, _) = window[0];
+        let (to, band, _) = window[1];
+        s.loans.accrue_interest(&position_id, &(to - from));
+        assert_eq!(s.manager.get_liquidation_band(&position_id), band, "debt {}", to);
+    }
+}
+
+#[test]
+fn keeper_intent_slice_escalates_per_band() {
+    for (debt, band, notional) in BAND_EDGES {
+        let s = setup();
+        let position_id = id(&s.env, 1);
+        open_position(&s, &position_id, debt);
+        assert_eq!(s.manager.get_liquidation_band(&position_id), band);
+
+        let keeper = Address::generate(&s.env);
+        if band.is_none() {
+            assert!(s.manager.try_trigger_liquidation(&position_id, &keeper).is_err());
+            continue;
+        }
+
+        let intent_id = s.manager.trigger_liquidation(&position_id, &keeper);
+        let intent = s.manager.get_intent(&intent_id).unwrap();
+        assert_eq!(intent.notional_to_raise, notional, "debt {}", debt);
+        assert_eq!(intent.collateral_to_sell, notional * PRICE_SCALE / PRICE);
+        assert_eq!(intent.keeper, Some(keeper));
+    }
+}
+
+#[test]
+fn admin_intent_is_capped_at_the_band_slice() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+
+    // The first band sells at most 25% of 200,000 of collateral
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 80_000, 1);
+    let intent = s.manager.get_intent(&intent_id).unwrap();
+    assert_eq!(intent.notional_to_raise, 50_000);
+    assert_eq!(intent.collateral_to_sell, 50_000 * PRICE_SCALE / PRICE);
+}
+
+#[test]
+#[should_panic(expected = "position healthy")]
+fn trigger_liquidation_rejects_position_at_first_band() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, 100_000);
+    s.manager.trigger_liquidation(&position_id, &Address::generate(&s.env));
+}
+
+#[test]
+#[should_panic(expected = "position healthy")]
+fn emit_intent_rejects_healthy_position() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, 100_000);
+    emit_intent(&s, &id(&s.env, 2), &position_id, 20_000, 1);
+}
+
+#[test]
+#[should_panic(expected = "position healthy")]
+fn start_auction_rejects_healthy_position() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, 100_000);
+    s.manager.start_auction(&id(&s.env, 5), &position_id, &20_000, &0, &1_000, &s.registry.get_version());
+}
+
+/// Auction of 20,000 units of a `DEBT` position, starting at the oracle
+/// price, and a funded whitelisted bidder
+fn start_auction(s: &Setup, auction_id: &BytesN<32>) -> Address {
+    let position_id = id(&s.env, 1);
+    open_position(s, &position_id, DEBT);
+    s.manager.start_auction(auction_id, &position_id, &20_000, &0, &1_000, &s.registry.get_version());
+    new_bidder(s)
+}
+
+fn new_bidder(s: &Setup) -> Address {
+    let bidder = Address::generate(&s.env);
+    s.manager.add_bidder(&bidder);
+    StellarAssetClient::new(&s.env, &s.stablecoin).mint(&bidder, &100_000);
+    bidder
+}
+
+#[test]
+fn bids_accumulate_into_per_bidder_claims() {
+    let s = setup();
+    let auction_id = id(&s.env, 5);
+    let first = start_auction(&s, &auction_id);
+    let second = new_bidder(&s);
+
+    assert_eq!(s.manager.bid(&auction_id, &first, &5_000), 10_000);
+    assert_eq!(s.manager.bid(&auction_id, &second, &2_000), 4_000);
+    assert_eq!(s.manager.bid(&auction_id, &first, &3_000), 6_000);
+
+    let claims = s.manager.get_auction_claims(&auction_id);
+    assert_eq!(claims.len(), 2);
+    let claim = claims.get(first.clone()).unwrap();
+    assert_eq!((claim.collateral_amount, claim.cost, claim.collateral_delivered), (8_000, 16_000, 0));
+    let claim = claims.get(second).unwrap();
+    assert_eq!((claim.collateral_amount, claim.cost, claim.collateral_delivered), (2_000, 4_000, 0));
+}
+
+#[test]
+fn settle_claim_delivers_the_outstanding_collateral() {
+    let s = setup();
+    let auction_id = id(&s.env, 5);
+    let bidder = start_auction(&s, &auction_id);
+    s.manager.bid(&auction_id, &bidder, &5_000);
+
+    s.manager.settle_claim(&auction_id, &bidder);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "ClaimSettled", auction_id.clone(), ClaimSettledEvent {
+                bidder: bidder.clone(),
+                collateral_amount: 5_000,
+            })
+        ]
+    );
+
+    // A later fill reopens the claim for just the new collateral
+    s.manager.bid(&auction_id, &bidder, &1_000);
+    s.manager.settle_claim(&auction_id, &bidder);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "ClaimSettled", auction_id.clone(), ClaimSettledEvent {
+                bidder: bidder.clone(),
+                collateral_amount: 1_000,
+            })
+        ]
+    );
+    let claim = s.manager.get_auction_claims(&auction_id).get(bidder).unwrap();
+    assert_eq!(claim.collateral_delivered, 6_000);
+}
+
+#[test]
+#[should_panic(expected = "claim already settled")]
+fn settled_claim_cannot_be_settled_again() {
+    let s = setup();
+    let auction_id = id(&s.env, 5);
+    let bidder = start_auction(&s, &auction_id);
+    s.manager.bid(&auction_id, &bidder, &5_000);
+
+    s.manager.settle_claim(&auction_id, &bidder);
+    s.manager.settle_claim(&auction_id, &bidder);
+}
+
+#[test]
+fn bid_cap_clears_the_debt_net_of_the_penalty() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+    let auction_id = id(&s.env, 5);
+    s.manager.start_auction(&auction_id, &position_id, &60_000, &0, &1_000, &s.registry.get_version());
+    let bidder = new_bidder(&s);
+    StellarAssetClient::new(&s.env, &s.stablecoin).mint(&bidder, &100_000);
+
+    // With no insurance fund only the 2.5% reward share is withheld, so
+    // 110,000 of debt needs 112,822 of proceeds: 56,411 units at 2
+    assert_eq!(s.manager.bid(&auction_id, &bidder, &60_000), 112_822);
+    assert_eq!(debt(&s, &position_id), 0);
+    let auction = s.manager.get_auction(&auction_id).unwrap();
+    assert!(auction.status == AuctionStatus::Filled);
+    assert_eq!(auction.collateral_remaining, 60_000 - 56_411);
+}
+
+#[test]
+#[should_panic(expected = "circuit breaker active")]
+fn bid_is_refused_while_the_circuit_breaker_is_on() {
+    let s = setup();
+    let auction_id = id(&s.env, 5);
+    let bidder = start_auction(&s, &auction_id);
+
+    s.registry.toggle_circuit_breaker(&s.asset, &true);
+    s.manager.bid(&auction_id, &bidder, &5_000);
+}
+
+#[test]
+#[should_panic(expected = "collateral exceeds attestation")]
+fn auction_cannot_offer_more_than_the_attested_collateral() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+    s.manager.start_auction(&id(&s.env, 5), &position_id, &(COLLATERAL + 1), &0, &1_000, &s.registry.get_version());
+}
+
+#[test]
+fn close_auction_emits_auction_closed() {
+    let s = setup();
+    let auction_id = id(&s.env, 5);
+    let bidder = start_auction(&s, &auction_id);
+    s.manager.bid(&auction_id, &bidder, &5_000);
+
+    s.manager.close_auction(&auction_id);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "AuctionClosed", auction_id.clone(), AuctionClosedEvent {
+                position_id: id(&s.env, 1),
+                collateral_remaining: 15_000,
+                proceeds: 10_000,
+            })
+        ]
+    );
+    assert!(s.manager.get_auction(&auction_id).unwrap().status == AuctionStatus::Closed);
+}
+
+/// Position with a receipt for 20,000 held in a one hour dispute window; the
+/// admin submitted it and escrowed the 500 penalty
+fn held_receipt(s: &Setup) -> (BytesN<32>, BytesN<32>) {
+    StellarAssetClient::new(&s.env, &s.stablecoin).mint(&s.admin, &1_000);
+    let position_id = id(&s.env, 1);
+    open_position(s, &position_id, DEBT);
+    let intent_id = id(&s.env, 2);
+    emit_intent(s, &intent_id, &position_id, 20_000, 1);
+    s.manager.accept_receipt(&intent_id, &20_000, &10_000, &1);
+    (position_id, intent_id)
+}
+
+fn stablecoin_balance(s: &Setup, address: &Address) -> i128 {
+    soroban_sdk::token::Client::new(&s.env, &s.stablecoin).balance(address)
+}
+
+#[test]
+fn unchallenged_receipt_settles_after_the_window() {
+    let s = setup_with_dispute_window(3_600);
+    let (position_id, intent_id) = held_receipt(&s);
+
+    let receipt = s.manager.get_pending_receipt(&intent_id).unwrap();
+    assert!(receipt.status == ReceiptStatus::Pending);
+    assert_eq!(receipt.challenge_deadline, 1_003_600);
+    assert_eq!(debt(&s, &position_id), DEBT);
+    assert_eq!(stablecoin_balance(&s, &s.manager.address), 500);
+
+    s.env.ledger().set_timestamp(1_003_601);
+    s.manager.finalize_receipt(&intent_id);
+    assert!(s.manager.get_pending_receipt(&intent_id).unwrap().status == ReceiptStatus::Settled);
+    assert_eq!(debt(&s, &position_id), DEBT - 19_500);
+    assert_eq!(stablecoin_balance(&s, &s.manager.address), 0);
+    assert_eq!(stablecoin_balance(&s, &s.admin), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "dispute window open")]
+fn receipt_cannot_settle_inside_the_window() {
+    let s = setup_with_dispute_window(3_600);
+    let (_, intent_id) = held_receipt(&s);
+    s.manager.finalize_receipt(&intent_id);
+}
+
+#[test]
+#[should_panic(expected = "venue not allowed")]
+fn finalize_rechecks_the_venue() {
+    let s = setup_with_dispute_window(3_600);
+    let (_, intent_id) = held_receipt(&s);
+
+    s.registry.remove_venue(&s.venue);
+    s.env.ledger().set_timestamp(1_003_601);
+    s.manager.finalize_receipt(&intent_id);
+}
+
+#[test]
+#[should_panic(expected = "circuit breaker active")]
+fn finalize_rechecks_the_circuit_breaker() {
+    let s = setup_with_dispute_window(3_600);
+    let (_, intent_id) = held_receipt(&s);
+
+    s.registry.toggle_circuit_breaker(&s.asset, &true);
+    s.env.ledger().set_timestamp(1_003_601);
+    s.manager.finalize_receipt(&intent_id);
+}
+
+#[test]
+fn guardian_challenge_rejects_and_refunds() {
+    let s = setup_with_dispute_window(3_600);
+    let (position_id, intent_id) = held_receipt(&s);
+    let guardian = Address::generate(&s.env);
+    s.manager.set_guardian(&guardian);
+
+    s.manager.challenge_receipt(&intent_id, &guardian, &Symbol::new(&s.env, "bad_fill"));
+    assert!(s.manager.get_pending_receipt(&intent_id).unwrap().status == ReceiptStatus::Rejected);
+    assert_eq!(stablecoin_balance(&s, &s.admin), 1_000);
+    assert_eq!(debt(&s, &position_id), DEBT);
+
+    // The intent is still live and takes a new receipt
+    assert!(s.manager.get_intent(&intent_id).unwrap().status.is_live());
+    s.manager.accept_receipt(&intent_id, &20_000, &10_000, &1);
+    assert!(s.manager.get_pending_receipt(&intent_id).unwrap().status == ReceiptStatus::Pending);
+}
+
+#[test]
+fn owner_challenge_waits_for_the_guardian() {
+    let s = setup_with_dispute_window(3_600);
+    let (position_id, intent_id) = held_receipt(&s);
+    s.manager.set_guardian(&Address::generate(&s.env));
+
+    s.manager.challenge_receipt(&intent_id, &s.owner, &Symbol::new(&s.env, "bad_fill"));
+    assert!(s.manager.get_pending_receipt(&intent_id).unwrap().status == ReceiptStatus::Challenged);
+
+    // Dismissing the challenge applies the receipt
+    s.manager.resolve_challenge(&intent_id, &false);
+    assert!(s.manager.get_pending_receipt(&intent_id).unwrap().status == ReceiptStatus::Settled);
+    assert_eq!(debt(&s, &position_id), DEBT - 19_500);
+}
+
+#[test]
+fn upheld_challenge_rejects_the_receipt() {
+    let s = setup_with_dispute_window(3_600);
+    let (position_id, intent_id) = held_receipt(&s);
+    s.manager.set_guardian(&Address::generate(&s.env));
+
+    s.manager.challenge_receipt(&intent_id, &s.owner, &Symbol::new(&s.env, "bad_fill"));
+    s.manager.resolve_challenge(&intent_id, &true);
+    assert!(s.manager.get_pending_receipt(&intent_id).unwrap().status == ReceiptStatus::Rejected);
+    assert_eq!(stablecoin_balance(&s, &s.admin), 1_000);
+    assert_eq!(debt(&s, &position_id), DEBT);
+}
+
+#[test]
+fn discard_receipt_refunds_the_escrow() {
+    let s = setup_with_dispute_window(3_600);
+    let (position_id, intent_id) = held_receipt(&s);
+
+    s.manager.discard_receipt(&s.admin, &intent_id);
+    assert!(s.manager.get_pending_receipt(&intent_id).unwrap().status == ReceiptStatus::Rejected);
+    assert_eq!(stablecoin_balance(&s, &s.admin), 1_000);
+    assert_eq!(debt(&s, &position_id), DEBT);
+
+    // Nothing is left to finalize
+    s.env.ledger().set_timestamp(1_003_601);
+    assert!(s.manager.try_finalize_receipt(&intent_id).is_err());
+}
+
+/// A new executor holding a bond of `bond`
+fn bonded_executor(s: &Setup, bond: i128) -> Address {
+    let executor = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.stablecoin).mint(&executor, &bond);
+    s.manager.post_bond(&executor, &bond);
+    executor
+}
+
+#[test]
+fn post_bond_registers_and_tops_up() {
+    let s = setup();
+    let executor = bonded_executor(&s, 4_000);
+    StellarAssetClient::new(&s.env, &s.stablecoin).mint(&executor, &1_000);
+    s.manager.post_bond(&executor, &1_000);
+
+    let record = s.manager.get_executor(&executor).unwrap();
+    assert_eq!(record.bond, 5_000);
+    assert_eq!(record.registered_at, 1_000_000);
+    assert_eq!(s.manager.get_total_bonded(), 5_000);
+    assert_eq!(stablecoin_balance(&s, &executor), 0);
+    assert_eq!(stablecoin_balance(&s, &s.manager.address), 5_000);
+}
+
+#[test]
+fn assign_intent_requires_the_minimum_bond() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 20_000, 1);
+
+    s.manager.set_min_executor_bond(&5_000);
+    let executor = bonded_executor(&s, 4_000);
+    assert!(s.manager.try_assign_intent(&intent_id, &executor).is_err());
+
+    StellarAssetClient::new(&s.env, &s.stablecoin).mint(&executor, &1_000);
+    s.manager.post_bond(&executor, &1_000);
+    s.manager.assign_intent(&intent_id, &executor);
+    assert_eq!(s.manager.get_assigned_executor(&intent_id), Some(executor.clone()));
+    assert_eq!(s.manager.get_executor(&executor).unwrap().active_assignments, vec![&s.env, intent_id.clone()]);
+
+    // A second executor cannot take an intent that is already assigned
+    let other = bonded_executor(&s, 5_000);
+    assert!(s.manager.try_assign_intent(&intent_id, &other).is_err());
+}
+
+#[test]
+fn withdraw_bond_waits_for_assignments_and_the_lock() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 20_000, 1);
+    let executor = bonded_executor(&s, 5_000);
+    s.manager.assign_intent(&intent_id, &executor);
+
+    assert!(s.manager.try_withdraw_bond(&executor, &5_000).is_err());
+
+    // Filling the intent releases the assignment and locks the bond for the
+    // default seven day challenge period
+    s.manager.accept_receipt(&intent_id, &20_000, &10_000, &1);
+    let record = s.manager.get_executor(&executor).unwrap();
+    assert!(record.active_assignments.is_empty());
+    assert_eq!(record.completed, 1);
+    assert_eq!(record.locked_until, 1_000_000 + 7 * 24 * 60 * 60);
+    assert!(s.manager.try_withdraw_bond(&executor, &5_000).is_err());
+
+    s.env.ledger().set_timestamp(record.locked_until);
+    s.manager.withdraw_bond(&executor, &5_000);
+    assert_eq!(s.manager.get_executor(&executor).unwrap().bond, 0);
+    assert_eq!(s.manager.get_total_bonded(), 0);
+    assert_eq!(stablecoin_balance(&s, &executor), 5_000);
+}
+
+#[test]
+fn slash_executor_pays_the_admin_and_releases_the_assignment() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 20_000, 1);
+    let executor = bonded_executor(&s, 5_000);
+    s.manager.assign_intent(&intent_id, &executor);
+
+    s.manager.slash_executor(&executor, &intent_id, &2_000, &Symbol::new(&s.env, "false_receipt"));
+    let record = s.manager.get_executor(&executor).unwrap();
+    assert_eq!(record.bond, 3_000);
+    assert_eq!(record.slash_count, 1);
+    assert_eq!(record.slashed_total, 2_000);
+    assert!(record.active_assignments.is_empty());
+    assert_eq!(record.locked_until, 1_000_000 + 7 * 24 * 60 * 60);
+    assert_eq!(s.manager.get_assigned_executor(&intent_id), None);
+    assert_eq!(s.manager.get_total_bonded(), 3_000);
+    assert_eq!(stablecoin_balance(&s, &s.admin), 2_000);
+
+    assert!(s.manager.try_slash_executor(&executor, &intent_id, &3_001, &Symbol::new(&s.env, "again")).is_err());
+}
+
+#[test]
+fn escrowed_penalties_are_counted_apart_from_bonds() {
+    let s = setup_with_dispute_window(3_600);
+    let executor = bonded_executor(&s, 5_000);
+    let (_, intent_id) = held_receipt(&s);
+    assert_eq!(s.manager.get_total_escrowed(), 500);
+    assert_eq!(s.manager.get_total_bonded(), 5_000);
+    assert_eq!(stablecoin_balance(&s, &s.manager.address), 5_500);
+
+    s.env.ledger().set_timestamp(1_003_601);
+    s.manager.finalize_receipt(&intent_id);
+    assert_eq!(s.manager.get_total_escrowed(), 0);
+    assert_eq!(s.manager.get_total_bonded(), 5_000);
+    assert_eq!(stablecoin_balance(&s, &s.manager.address), 5_000);
+    assert_eq!(s.manager.get_executor(&executor).unwrap().bond, 5_000);
+}
+
+#[test]
+fn shortfall_cover_emits_shortfall_covered() {
+    let s = setup();
+    let (position_id, intent_id, _) = sold_out_intent(&s);
+
+    s.manager.accept_receipt(&intent_id, &COLLATERAL, &COLLATERAL, &2);
+    assert_eq!(
+        manager_events(&s),
+        vec![
+            &s.env,
+            event(&s, "PenaltyCharged", position_id.clone(), PenaltyChargedEvent {
+                proceeds: COLLATERAL,
+                penalty: 5_000,
+                reward: 2_500,
+                reward_to: Some(s.admin.clone()),
+                insurance: 2_500,
+            }),
+            event(&s, "ShortfallCovered", position_id.clone(), ShortfallCoveredEvent { shortfall: 15_000, covered: 12_500 }),
+            event(&s, "ReceiptAccepted", intent_id.clone(), ReceiptAcceptedEvent {
+                position_id: position_id.clone(),
+                proceeds: COLLATERAL,
+                oracle_round: 2,
+            }),
+            event(&s, "IntentFilled", intent_id, IntentFilledEvent {
+                position_id: position_id.clone(),
+                proceeds_raised: COLLATERAL,
+                collateral_sold: COLLATERAL,
+            }),
+            event(&s, "CooldownStarted", position_id, CooldownStartedEvent { started_at: 1_000_000 + 24 * 60 * 60 })
+        ]
+    );
+}
+
+#[test]
+fn dispute_flow_emits_receipt_events() {
+    let s = setup_with_dispute_window(3_600);
+    let (position_id, intent_id) = held_receipt(&s);
+    assert_eq!(
+        manager_events(&s),
+        vec![
+            &s.env,
+            event(&s, "ReceiptPending", intent_id.clone(), ReceiptPendingEvent {
+                position_id: position_id.clone(),
+                proceeds: 20_000,
+                collateral_sold: 10_000,
+                challenge_deadline: 1_003_600,
+            })
+        ]
+    );
+
+    let reason = Symbol::new(&s.env, "bad_price");
+    s.manager.challenge_receipt(&intent_id, &s.owner, &reason);
+    assert_eq!(
+        manager_events(&s),
+        vec![
+            &s.env,
+            event(&s, "ReceiptChallenged", intent_id.clone(), ReceiptChallengedEvent {
+                position_id: position_id.clone(),
+                submitter: s.admin.clone(),
+                challenger: s.owner.clone(),
+                reason,
+            })
+        ]
+    );
+
+    s.manager.set_guardian(&Address::generate(&s.env));
+    s.manager.resolve_challenge(&intent_id, &true);
+    assert_eq!(
+        manager_events(&s),
+        vec![
+            &s.env,
+            event(&s, "ReceiptRejected", intent_id, ReceiptRejectedEvent {
+                position_id,
+                submitter: s.admin.clone(),
+                refunded: 500,
+            })
+        ]
+    );
+}
+
+#[test]
+fn executor_changes_emit_their_events() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_position(&s, &position_id, DEBT);
+    let intent_id = id(&s.env, 2);
+    emit_intent(&s, &intent_id, &position_id, 20_000, 1);
+
+    let executor = bonded_executor(&s, 5_000);
+    assert_eq!(
+        manager_events(&s),
+        vec![&s.env, event(&s, "BondPosted", executor.clone(), BondUpdatedEvent { amount: 5_000, bond: 5_000 })]
+    );
+
+    s.manager.assign_intent(&intent_id, &executor);
+    assert_eq!(
+        manager_events(&s),
+        vec![&s.env, event(&s, "IntentAssigned", intent_id.clone(), IntentAssignedEvent { executor: executor.clone() })]
+    );
+
+    let reason = Symbol::new(&s.env, "missed_deadline");
+    s.manager.slash_executor(&executor, &intent_id, &2_000, &reason);
+    assert_eq!(
+        manager_events(&s),
+        vec![
+            &s.env,
+            event(&s, "ExecutorSlashed", executor.clone(), ExecutorSlashedEvent { intent_id, amount: 2_000, reason })
+        ]
+    );
+
+    s.env.ledger().set_timestamp(1_000_000 + 7 * 24 * 60 * 60);
+    s.manager.withdraw_bond(&executor, &3_000);
+    assert_eq!(
+        manager_events(&s),
+        vec![&s.env, event(&s, "BondWithdrawn", executor, BondUpdatedEvent { amount: 3_000, bond: 0 })]
+    );
+}
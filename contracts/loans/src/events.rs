@@ -0,0 +1,106 @@
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, Symbol, Val};
+
+/// Schema version in every event topic, bumped when a payload changes shape
+pub const EVENT_VERSION: u32 = 1;
+
+const CONTRACT: &str = "loans";
+
+/// Publish `data` under `(contract, event name, EVENT_VERSION, primary id)`
+pub(crate) fn publish<I, D>(env: &Env, name: &str, id: I, data: D)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    env.events().publish(
+        (Symbol::new(env, CONTRACT), Symbol::new(env, name), EVENT_VERSION, id),
+        data,
+    );
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionOpenedEvent {
+    pub owner: Address,
+    pub asset: Symbol,
+    pub collateral_ref: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BorrowedEvent {
+    pub owner: Address,
+    pub amount: i128,
+    pub oracle_round: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RepaidEvent {
+    pub payer: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InterestAccruedEvent {
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DebtReducedEvent {
+    pub proceeds: i128,
+    pub oracle_round: i128,
+    pub receipt_nonce: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LtvUpdatedEvent {
+    pub ltv_bps: i128,
+    pub oracle_round: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionClosedEvent {
+    pub owner: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionSplitEvent {
+    pub new_position_id: BytesN<32>,
+    pub collateral_share: i128,
+    pub debt_share: i128,
+    pub ltv_bps: i128,
+    pub new_ltv_bps: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionsMergedEvent {
+    pub merged_position_id: BytesN<32>,
+    pub ltv_bps: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustodianUpdatedEvent {
+    pub allowed: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralAttestedEvent {
+    pub custodian: BytesN<32>,
+    pub amount: i128,
+    pub vault_ref: BytesN<32>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PositionReopenedEvent {
+    pub owner: Address,
+}
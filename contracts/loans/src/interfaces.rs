@@ -0,0 +1,38 @@
+use soroban_sdk::{contractclient, contracttype, Env, Symbol, Vec};
+
+// Client bindings for the contracts Loans reads from. The types mirror the
+// callee's #[contracttype] definitions field for field so values decode as-is.
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Policy {
+    pub max_ltv_bps: i128,
+    pub bands: Vec<i128>,
+    pub slice_pct_bps: i128,
+    pub cooldown_seconds: i128,
+    pub max_slippage_bps: i128,
+    pub staleness_seconds: i128,
+    pub interest_base_bps: i128,
+    pub spread_bps: i128,
+    pub allowed: bool,
+    pub circuit_breaker: bool,
+}
+
+#[contractclient(name = "PolicyRegistryClient")]
+pub trait PolicyRegistryInterface {
+    fn get_policy(env: Env, asset: Symbol) -> Option<Policy>;
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct PriceRound {
+    pub price: i128,
+    pub timestamp: u64,
+    pub round_id: i128,
+    pub source: Symbol,
+}
+
+#[contractclient(name = "PriceAdapterClient")]
+pub trait PriceAdapterInterface {
+    fn get_price_if_fresh(env: Env, asset: Symbol, max_age_seconds: u64) -> Option<PriceRound>;
+}
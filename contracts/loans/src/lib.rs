@@ -1,5 +1,14 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, Map, BytesN};
+use soroban_sdk::{contract, contractimpl, contracttype, xdr::ToXdr, Address, Bytes, Env, Symbol, Map, BytesN, Vec};
+
+mod events;
+pub mod interfaces;
+
+#[cfg(test)]
+mod test;
+
+pub use events::*;
+use interfaces::{Policy, PolicyRegistryClient, PriceAdapterClient};
 
 #[contracttype]
 #[derive(Clone, PartialEq)]
@@ -25,6 +34,61 @@ pub struct Position {
     pub created_at: u64,
 }
 
+/// Collateral balance signed off by a registered custodian
+#[contracttype]
+#[derive(Clone)]
+pub struct Attestation {
+    pub custodian: BytesN<32>,
+    pub amount: i128,
+    pub vault_ref: BytesN<32>,
+    pub timestamp: u64,
+}
+
+/// Debt against attested collateral, valued at the current oracle price
+#[contracttype]
+#[derive(Clone)]
+pub struct PositionHealth {
+    pub debt: i128,
+    pub collateral_value: i128,
+    pub ltv_bps: i128,
+}
+
+/// Effect of applying liquidation proceeds to a position. Shared by
+/// `apply_liquidation` and `preview_liquidation` so the two cannot drift.
+#[contracttype]
+#[derive(Clone)]
+pub struct LiquidationOutcome {
+    pub interest_paid: i128,
+    pub principal_paid: i128,
+    pub surplus: i128,
+    pub principal: i128,
+    pub accrued_interest: i128,
+    pub status: PositionStatus,
+}
+
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum HistoryAction {
+    Open,
+    Draw,
+    Repay,
+    Interest,
+    Liquidation,
+    Close,
+    Split,
+    Merge,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct HistoryEntry {
+    pub action: HistoryAction,
+    pub timestamp: u64,
+    pub amount: i128,
+    pub actor: Address,
+    pub oracle_round: i128,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -34,8 +98,39 @@ pub enum DataKey {
     PolicyRegistry,
     PriceAdapter,
     LiquidationManager,
+    History(BytesN<32>, u32),
+    HistoryCounts,
+    Custodians,
+    Attestation(BytesN<32>),
+    Cooldowns,
 }
 
+/// Per-item result of the batched admin entrypoints.
+#[contracttype]
+#[derive(Clone, PartialEq)]
+pub enum BatchOutcome {
+    Applied,
+    NotFound,
+    Closed,
+    InvalidAmount,
+}
+
+const BPS: i128 = 10_000;
+
+/// Oracle prices are integers scaled by 1e7
+const PRICE_SCALE: i128 = 10_000_000;
+
+/// Domain tag prepended to every custodian attestation message
+const ATTESTATION_DOMAIN: &[u8] = b"legasi:attest:v2";
+
+/// Oldest entries are overwritten once a position's history reaches this length.
+const MAX_HISTORY_ENTRIES: u32 = 100;
+
+/// Upper bound on positions per batched call. Each position writes one
+/// history entry on top of the instance and auth nonce, so a batch stays
+/// under the 25 ledger writes a Soroban transaction allows.
+const MAX_BATCH_SIZE: u32 = 20;
+
 #[contract]
 pub struct Loans;
 
@@ -58,17 +153,56 @@ impl Loans {
         env.storage().instance().set(&DataKey::LiquidationManager, &liquidation_manager);
     }
 
-    fn require_admin(env: &Env) {
+    fn require_admin(env: &Env) -> Address {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("admin not set");
         admin.require_auth();
+        admin
     }
 
-    fn require_liquidation_manager(env: &Env) {
+    fn require_liquidation_manager(env: &Env) -> Address {
         let liquidation_manager: Address = env.storage()
             .instance()
             .get(&DataKey::LiquidationManager)
             .expect("liquidation manager not set");
         liquidation_manager.require_auth();
+        liquidation_manager
+    }
+
+    /// Append an entry to a position's history. Entries live in a ring of
+    /// `MAX_HISTORY_ENTRIES` slots, one ledger entry each, so recording one
+    /// never rewrites the rest of the history.
+    fn record_history(
+        env: &Env,
+        position_id: &BytesN<32>,
+        action: HistoryAction,
+        amount: i128,
+        actor: Address,
+        oracle_round: i128
+    ) {
+        let mut counts = Self::history_counts(env);
+        let count = counts.get(position_id.clone()).unwrap_or(0);
+
+        let entry = HistoryEntry {
+            action,
+            timestamp: env.ledger().timestamp(),
+            amount,
+            actor,
+            oracle_round,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::History(position_id.clone(), count % MAX_HISTORY_ENTRIES), &entry);
+
+        counts.set(position_id.clone(), count + 1);
+        env.storage().instance().set(&DataKey::HistoryCounts, &counts);
+    }
+
+    /// Entries ever recorded per position, including overwritten ones
+    fn history_counts(env: &Env) -> Map<BytesN<32>, u32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::HistoryCounts)
+            .unwrap_or(Map::new(env))
     }
 
     /// Open a new position
@@ -85,7 +219,7 @@ impl Loans {
             owner: owner.clone(),
             principal: 0,
             accrued_interest: 0,
-            collateral_ref,
+            collateral_ref: collateral_ref.clone(),
             asset: asset.clone(),
             ltv_bps: 0,
             status: PositionStatus::Open,
@@ -99,22 +233,31 @@ impl Loans {
             .get(&DataKey::Positions)
             .unwrap_or(Map::new(&env));
         
+        // Ids are never reused: attestations and history are keyed by id
+        if positions.contains_key(position_id.clone()) {
+            panic!("position already exists");
+        }
+        
         positions.set(position_id.clone(), position);
         env.storage().instance().set(&DataKey::Positions, &positions);
 
-        env.events().publish(
-            (Symbol::new(&env, "PositionOpened"),),
-            (position_id, owner, asset)
+        Self::record_history(&env, &position_id, HistoryAction::Open, 0, owner.clone(), 0);
+
+        events::publish(
+            &env,
+            "PositionOpened",
+            position_id,
+            PositionOpenedEvent { owner, asset, collateral_ref },
         );
     }
 
-    /// Draw (borrow) from a position
+    /// Draw (borrow) from a position. The stored LTV is recomputed from the
+    /// attested collateral at the current price.
     pub fn draw(
         env: Env,
         position_id: BytesN<32>,
         amount: i128,
-        oracle_round: i128,
-        new_ltv_bps: i128
+        oracle_round: i128
     ) {
         if amount <= 0 {
             panic!("invalid amount");
@@ -130,21 +273,43 @@ impl Loans {
         // Require position owner authorization
         position.owner.require_auth();
 
+        if Self::cooldown_elapsed(&env, &position_id, &position) {
+            Self::reopen(&env, &position_id, &mut position);
+        }
         if position.status != PositionStatus::Open {
             panic!("position not open");
         }
 
+        // Draws are only allowed against attested collateral
+        let debt_after = position.principal + position.accrued_interest + amount;
+        let policy = Self::policy(&env, &position.asset);
+        let collateral_value = Self::attested_collateral_value(&env, &position_id, &position.asset, &policy);
+        if debt_after * BPS > collateral_value * policy.max_ltv_bps {
+            panic!("insufficient attested collateral");
+        }
+
         // Update position
         position.principal += amount;
-        position.ltv_bps = new_ltv_bps;
+        position.ltv_bps = debt_after * BPS / collateral_value;
         position.last_oracle_round = oracle_round;
 
         positions.set(position_id.clone(), position.clone());
         env.storage().instance().set(&DataKey::Positions, &positions);
 
-        env.events().publish(
-            (Symbol::new(&env, "Borrowed"),),
-            (position_id, position.owner, amount, oracle_round)
+        Self::record_history(
+            &env,
+            &position_id,
+            HistoryAction::Draw,
+            amount,
+            position.owner.clone(),
+            oracle_round,
+        );
+
+        events::publish(
+            &env,
+            "Borrowed",
+            position_id,
+            BorrowedEvent { owner: position.owner, amount, oracle_round },
         );
     }
 
@@ -194,50 +359,86 @@ impl Loans {
             position.status = PositionStatus::Closable;
         }
 
+        let oracle_round = position.last_oracle_round;
         positions.set(position_id.clone(), position);
         env.storage().instance().set(&DataKey::Positions, &positions);
 
-        env.events().publish(
-            (Symbol::new(&env, "Repaid"),),
-            (position_id, payer, amount)
+        Self::record_history(
+            &env,
+            &position_id,
+            HistoryAction::Repay,
+            amount,
+            payer.clone(),
+            oracle_round,
         );
+
+        events::publish(&env, "Repaid", position_id, RepaidEvent { payer, amount });
     }
 
-    /// Accrue interest on a position
+    /// Accrue interest on a position. Unlike the batch, this applies any
+    /// amount to any existing position, as it always has.
     pub fn accrue_interest(
         env: Env,
         position_id: BytesN<32>,
         interest_amount: i128
     ) {
-        Self::require_admin(&env);
+        let admin = Self::require_admin(&env);
 
         let mut positions: Map<BytesN<32>, Position> = env.storage()
             .instance()
             .get(&DataKey::Positions)
             .unwrap_or(Map::new(&env));
-        
-        let mut position = positions.get(position_id.clone()).expect("position not found");
 
-        position.accrued_interest += interest_amount;
+        Self::apply_interest(&env, &mut positions, &position_id, interest_amount, &admin);
 
-        positions.set(position_id.clone(), position);
         env.storage().instance().set(&DataKey::Positions, &positions);
+    }
 
-        env.events().publish(
-            (Symbol::new(&env, "InterestAccrued"),),
-            (position_id, interest_amount)
-        );
+    /// Accrue interest on many positions in one call (admin only).
+    /// `interest_amounts[i]` is applied to `position_ids[i]`; positions that are
+    /// missing or closed are skipped and reported instead of aborting the batch.
+    pub fn accrue_interest_batch(
+        env: Env,
+        position_ids: Vec<BytesN<32>>,
+        interest_amounts: Vec<i128>
+    ) -> Vec<BatchOutcome> {
+        let admin = Self::require_admin(&env);
+        Self::check_batch(position_ids.len(), interest_amounts.len());
+
+        let mut positions: Map<BytesN<32>, Position> = env.storage()
+            .instance()
+            .get(&DataKey::Positions)
+            .unwrap_or(Map::new(&env));
+
+        let mut outcomes = Vec::new(&env);
+        for (position_id, interest_amount) in position_ids.iter().zip(interest_amounts.iter()) {
+            let outcome = Self::batch_outcome(&positions, &position_id, interest_amount > 0);
+            if outcome == BatchOutcome::Applied {
+                Self::apply_interest(&env, &mut positions, &position_id, interest_amount, &admin);
+            }
+            outcomes.push_back(outcome);
+        }
+
+        env.storage().instance().set(&DataKey::Positions, &positions);
+        outcomes
     }
 
-    /// Apply liquidation (privileged - only LiquidationManager)
+    /// Apply liquidation (privileged - only LiquidationManager). The
+    /// `collateral_sold` to raise `proceeds` is taken off the position's
+    /// attested collateral, so it cannot be borrowed against again before the
+    /// custodian attests a new balance.
     pub fn apply_liquidation(
         env: Env,
         position_id: BytesN<32>,
         proceeds: i128,
+        collateral_sold: i128,
         oracle_round: i128,
         receipt_nonce: i128
     ) {
-        Self::require_liquidation_manager(&env);
+        let liquidation_manager = Self::require_liquidation_manager(&env);
+        if collateral_sold < 0 {
+            panic!("invalid amount");
+        }
 
         let mut positions: Map<BytesN<32>, Position> = env.storage()
             .instance()
@@ -246,51 +447,416 @@ impl Loans {
         
         let mut position = positions.get(position_id.clone()).expect("position not found");
 
-        // Reduce debt (apply to interest first, then principal)
-        let mut remaining = proceeds;
+        let outcome = Self::liquidation_outcome(&position, proceeds);
+        position.principal = outcome.principal;
+        position.accrued_interest = outcome.accrued_interest;
+        position.status = outcome.status;
+        position.last_oracle_round = oracle_round;
+        position.nonce += receipt_nonce;
+
+        if position.status == PositionStatus::Closed {
+            events::publish(
+                &env,
+                "PositionClosed",
+                position_id.clone(),
+                PositionClosedEvent { owner: position.owner.clone() },
+            );
+        } else {
+            let mut cooldowns: Map<BytesN<32>, u64> = env.storage()
+                .instance()
+                .get(&DataKey::Cooldowns)
+                .unwrap_or(Map::new(&env));
+            cooldowns.set(position_id.clone(), env.ledger().timestamp());
+            env.storage().instance().set(&DataKey::Cooldowns, &cooldowns);
+        }
 
-        if position.accrued_interest > 0 {
-            if remaining >= position.accrued_interest {
-                remaining -= position.accrued_interest;
-                position.accrued_interest = 0;
-            } else {
-                position.accrued_interest -= remaining;
-                remaining = 0;
+        let closed = position.status == PositionStatus::Closed;
+        positions.set(position_id.clone(), position);
+        env.storage().instance().set(&DataKey::Positions, &positions);
+
+        if let Some(mut attestation) = Self::get_attestation(env.clone(), position_id.clone()) {
+            if collateral_sold > 0 {
+                attestation.amount = (attestation.amount - collateral_sold).max(0);
+                env.storage().persistent().set(&DataKey::Attestation(position_id.clone()), &attestation);
             }
         }
 
-        if remaining > 0 {
-            if remaining >= position.principal {
-                position.principal = 0;
-            } else {
-                position.principal -= remaining;
+        Self::record_history(
+            &env,
+            &position_id,
+            HistoryAction::Liquidation,
+            proceeds,
+            liquidation_manager.clone(),
+            oracle_round,
+        );
+        if closed {
+            Self::record_history(
+                &env,
+                &position_id,
+                HistoryAction::Close,
+                0,
+                liquidation_manager,
+                oracle_round,
+            );
+        }
+
+        events::publish(
+            &env,
+            "DebtReduced",
+            position_id,
+            DebtReducedEvent { proceeds, oracle_round, receipt_nonce },
+        );
+    }
+
+    /// Register a custodian ed25519 public key (admin only)
+    pub fn add_custodian(env: Env, custodian: BytesN<32>) {
+        Self::require_admin(&env);
+
+        let mut custodians: Vec<BytesN<32>> = env.storage()
+            .instance()
+            .get(&DataKey::Custodians)
+            .unwrap_or(Vec::new(&env));
+
+        if !custodians.contains(&custodian) {
+            custodians.push_back(custodian.clone());
+            env.storage().instance().set(&DataKey::Custodians, &custodians);
+        }
+
+        events::publish(&env, "CustodianUpdated", custodian, CustodianUpdatedEvent { allowed: true });
+    }
+
+    /// Remove a custodian public key (admin only). Existing attestations stay on record.
+    pub fn remove_custodian(env: Env, custodian: BytesN<32>) {
+        Self::require_admin(&env);
+
+        let mut custodians: Vec<BytesN<32>> = env.storage()
+            .instance()
+            .get(&DataKey::Custodians)
+            .unwrap_or(Vec::new(&env));
+
+        if let Some(index) = custodians.first_index_of(&custodian) {
+            custodians.remove(index);
+            env.storage().instance().set(&DataKey::Custodians, &custodians);
+        }
+
+        events::publish(&env, "CustodianUpdated", custodian, CustodianUpdatedEvent { allowed: false });
+    }
+
+    pub fn is_custodian(env: Env, custodian: BytesN<32>) -> bool {
+        let custodians: Vec<BytesN<32>> = env.storage()
+            .instance()
+            .get(&DataKey::Custodians)
+            .unwrap_or(Vec::new(&env));
+        custodians.contains(&custodian)
+    }
+
+    /// Record a custodian-signed collateral balance for a position.
+    /// The signature covers `ATTESTATION_DOMAIN || contract address (XDR) ||
+    /// position_id || vault_ref || amount (i128 big-endian) || timestamp (u64
+    /// big-endian)` and must come from a registered custodian. Binding the
+    /// contract address stops a signature being replayed on another
+    /// deployment. Each attestation must be newer than the last one.
+    pub fn attest_collateral(
+        env: Env,
+        position_id: BytesN<32>,
+        custodian: BytesN<32>,
+        amount: i128,
+        vault_ref: BytesN<32>,
+        timestamp: u64,
+        signature: BytesN<64>
+    ) {
+        let attestation = Attestation { custodian, amount, vault_ref, timestamp };
+        let position = Self::get_position(env.clone(), position_id.clone()).expect("position not found");
+        if position.collateral_ref != attestation.vault_ref {
+            panic!("vault mismatch");
+        }
+        if let Some(previous) = Self::get_attestation(env.clone(), position_id.clone()) {
+            if attestation.timestamp <= previous.timestamp {
+                panic!("stale attestation");
             }
         }
 
-        position.last_oracle_round = oracle_round;
-        position.nonce += receipt_nonce;
+        Self::store_attestation(&env, &position_id, attestation, &signature);
+    }
 
-        // Update status
-        if position.principal == 0 && position.accrued_interest == 0 {
-            position.status = PositionStatus::Closed;
-            
-            env.events().publish(
-                (Symbol::new(&env, "PositionClosed"),),
-                position_id.clone()
-            );
-        } else {
-            position.status = PositionStatus::InLiquidationCooldown;
+    /// Check a custodian's signature over `attestation` for `position_id`,
+    /// then record it as the position's collateral
+    fn store_attestation(env: &Env, position_id: &BytesN<32>, attestation: Attestation, signature: &BytesN<64>) {
+        if attestation.amount < 0 {
+            panic!("invalid amount");
+        }
+        if !Self::is_custodian(env.clone(), attestation.custodian.clone()) {
+            panic!("unknown custodian");
+        }
+        if attestation.timestamp > env.ledger().timestamp() {
+            panic!("attestation from the future");
         }
 
-        positions.set(position_id.clone(), position);
+        let mut message = Bytes::from_slice(env, ATTESTATION_DOMAIN);
+        message.append(&env.current_contract_address().to_xdr(env));
+        message.append(&position_id.clone().into());
+        message.append(&attestation.vault_ref.clone().into());
+        message.extend_from_array(&attestation.amount.to_be_bytes());
+        message.extend_from_array(&attestation.timestamp.to_be_bytes());
+        env.crypto().ed25519_verify(&attestation.custodian, &message, signature);
+
+        env.storage().persistent().set(&DataKey::Attestation(position_id.clone()), &attestation);
+
+        events::publish(
+            env,
+            "CollateralAttested",
+            position_id.clone(),
+            CollateralAttestedEvent {
+                custodian: attestation.custodian,
+                amount: attestation.amount,
+                vault_ref: attestation.vault_ref,
+                timestamp: attestation.timestamp,
+            },
+        );
+    }
+
+    /// Latest attested collateral for a position (view function)
+    pub fn get_attestation(env: Env, position_id: BytesN<32>) -> Option<Attestation> {
+        env.storage().persistent().get(&DataKey::Attestation(position_id))
+    }
+
+    /// Move `collateral_share` and `debt_share` (both in bps) of a position into a
+    /// new position `new_id` with the same owner and asset, held in the vault
+    /// of `attestation`. Accrued interest moves with the debt and
+    /// `collateral_share` of the attested collateral leaves the position.
+    /// `attestation` is the custodian's fresh, signed balance for `new_id`
+    /// (see `attest_collateral`), newer than the position's own; on the same
+    /// vault it cannot exceed the collateral moved. Both resulting LTVs,
+    /// valued at the current price, must stay within the asset's `max_ltv_bps`.
+    pub fn split_position(
+        env: Env,
+        position_id: BytesN<32>,
+        new_id: BytesN<32>,
+        collateral_share: i128,
+        debt_share: i128,
+        attestation: Attestation,
+        signature: BytesN<64>
+    ) {
+        if collateral_share <= 0 || collateral_share >= BPS {
+            panic!("invalid collateral share");
+        }
+        if !(0..=BPS).contains(&debt_share) {
+            panic!("invalid debt share");
+        }
+
+        let mut positions: Map<BytesN<32>, Position> = env.storage()
+            .instance()
+            .get(&DataKey::Positions)
+            .unwrap_or(Map::new(&env));
+
+        let mut position = positions.get(position_id.clone()).expect("position not found");
+
+        position.owner.require_auth();
+
+        if position.status != PositionStatus::Open {
+            panic!("position not open");
+        }
+        if positions.contains_key(new_id.clone()) {
+            panic!("position already exists");
+        }
+
+        let moved_principal = position.principal * debt_share / BPS;
+        let moved_interest = position.accrued_interest * debt_share / BPS;
+
+        let mut new_position = Position {
+            owner: position.owner.clone(),
+            principal: moved_principal,
+            accrued_interest: moved_interest,
+            collateral_ref: attestation.vault_ref.clone(),
+            asset: position.asset.clone(),
+            ltv_bps: 0,
+            status: PositionStatus::Open,
+            last_oracle_round: position.last_oracle_round,
+            nonce: 0,
+            created_at: env.ledger().timestamp(),
+        };
+
+        position.principal -= moved_principal;
+        position.accrued_interest -= moved_interest;
+
+        // The collateral share leaves the position; the new position holds
+        // whatever the custodian now attests to
+        let mut kept = Self::get_attestation(env.clone(), position_id.clone()).expect("collateral not attested");
+        if attestation.timestamp <= kept.timestamp {
+            panic!("stale attestation");
+        }
+        let moved_amount = kept.amount * collateral_share / BPS;
+        if attestation.vault_ref == kept.vault_ref && attestation.amount > moved_amount {
+            panic!("attestation exceeds collateral moved");
+        }
+        kept.amount -= moved_amount;
+        env.storage().persistent().set(&DataKey::Attestation(position_id.clone()), &kept);
+        Self::store_attestation(&env, &new_id, attestation, &signature);
+
+        let policy = Self::policy(&env, &position.asset);
+        let kept_ltv_bps = Self::position_health(&env, &position_id, &position, &policy).ltv_bps;
+        let new_ltv_bps = Self::position_health(&env, &new_id, &new_position, &policy).ltv_bps;
+        if new_ltv_bps > policy.max_ltv_bps || kept_ltv_bps > policy.max_ltv_bps {
+            panic!("ltv exceeds policy");
+        }
+        position.ltv_bps = kept_ltv_bps;
+        new_position.ltv_bps = new_ltv_bps;
+
+        positions.set(position_id.clone(), position.clone());
+        positions.set(new_id.clone(), new_position);
         env.storage().instance().set(&DataKey::Positions, &positions);
 
-        env.events().publish(
-            (Symbol::new(&env, "DebtReduced"),),
-            (position_id, proceeds)
+        let moved_debt = moved_principal + moved_interest;
+        Self::record_history(
+            &env,
+            &position_id,
+            HistoryAction::Split,
+            moved_debt,
+            position.owner.clone(),
+            position.last_oracle_round,
+        );
+        Self::record_history(
+            &env,
+            &new_id,
+            HistoryAction::Split,
+            moved_debt,
+            position.owner.clone(),
+            position.last_oracle_round,
+        );
+
+        events::publish(
+            &env,
+            "PositionSplit",
+            position_id,
+            PositionSplitEvent {
+                new_position_id: new_id,
+                collateral_share,
+                debt_share,
+                ltv_bps: kept_ltv_bps,
+                new_ltv_bps,
+            },
         );
     }
 
+    /// Fold position `b` into position `a`. Both must be open and share an owner,
+    /// asset and collateral reference; `b` is closed and the merged LTV, valued
+    /// at the current price, must stay within policy.
+    pub fn merge_positions(env: Env, a: BytesN<32>, b: BytesN<32>) {
+        if a == b {
+            panic!("cannot merge a position with itself");
+        }
+
+        let mut positions: Map<BytesN<32>, Position> = env.storage()
+            .instance()
+            .get(&DataKey::Positions)
+            .unwrap_or(Map::new(&env));
+
+        let mut target = positions.get(a.clone()).expect("position not found");
+        let mut source = positions.get(b.clone()).expect("position not found");
+
+        target.owner.require_auth();
+
+        // Attestations are per vault, so only slices of one vault can be pooled
+        if target.owner != source.owner
+            || target.asset != source.asset
+            || target.collateral_ref != source.collateral_ref
+        {
+            panic!("positions not compatible");
+        }
+        if target.status != PositionStatus::Open || source.status != PositionStatus::Open {
+            panic!("position not open");
+        }
+
+        let moved_debt = source.principal + source.accrued_interest;
+
+        target.principal += source.principal;
+        target.accrued_interest += source.accrued_interest;
+        target.last_oracle_round = target.last_oracle_round.max(source.last_oracle_round);
+
+        // Attested collateral is pooled under `a`, dated by the newer
+        // attestation so older signatures for `a` cannot be replayed over it
+        if let Some(source_attestation) = Self::get_attestation(env.clone(), b.clone()) {
+            let merged = match Self::get_attestation(env.clone(), a.clone()) {
+                Some(mut attestation) => {
+                    attestation.amount += source_attestation.amount;
+                    attestation.timestamp = attestation.timestamp.max(source_attestation.timestamp);
+                    attestation
+                }
+                None => source_attestation,
+            };
+            env.storage().persistent().set(&DataKey::Attestation(a.clone()), &merged);
+            env.storage().persistent().remove(&DataKey::Attestation(b.clone()));
+        }
+
+        let policy = Self::policy(&env, &target.asset);
+        target.ltv_bps = Self::position_health(&env, &a, &target, &policy).ltv_bps;
+        if target.ltv_bps > policy.max_ltv_bps {
+            panic!("ltv exceeds policy");
+        }
+
+        source.principal = 0;
+        source.accrued_interest = 0;
+        source.ltv_bps = 0;
+        source.status = PositionStatus::Closed;
+
+        positions.set(a.clone(), target.clone());
+        positions.set(b.clone(), source);
+        env.storage().instance().set(&DataKey::Positions, &positions);
+
+        Self::record_history(
+            &env,
+            &a,
+            HistoryAction::Merge,
+            moved_debt,
+            target.owner.clone(),
+            target.last_oracle_round,
+        );
+        Self::record_history(
+            &env,
+            &b,
+            HistoryAction::Merge,
+            moved_debt,
+            target.owner.clone(),
+            target.last_oracle_round,
+        );
+
+        events::publish(
+            &env,
+            "PositionsMerged",
+            a,
+            PositionsMergedEvent {
+                merged_position_id: b.clone(),
+                ltv_bps: target.ltv_bps,
+            },
+        );
+        events::publish(
+            &env,
+            "PositionClosed",
+            b,
+            PositionClosedEvent { owner: target.owner },
+        );
+    }
+
+    /// Return a position in liquidation cooldown to `Open` once the asset's
+    /// `Policy.cooldown_seconds` have passed since its last liquidation.
+    /// Anyone may call this; returns whether the position was reopened.
+    pub fn end_cooldown(env: Env, position_id: BytesN<32>) -> bool {
+        let mut positions: Map<BytesN<32>, Position> = env.storage()
+            .instance()
+            .get(&DataKey::Positions)
+            .unwrap_or(Map::new(&env));
+
+        let mut position = positions.get(position_id.clone()).expect("position not found");
+        if !Self::cooldown_elapsed(&env, &position_id, &position) {
+            return false;
+        }
+
+        Self::reopen(&env, &position_id, &mut position);
+        positions.set(position_id, position);
+        env.storage().instance().set(&DataKey::Positions, &positions);
+        true
+    }
+
     /// Close a position (must be fully repaid)
     pub fn close_position(env: Env, position_id: BytesN<32>) {
         let mut positions: Map<BytesN<32>, Position> = env.storage()
@@ -311,12 +877,23 @@ impl Loans {
         }
 
         position.status = PositionStatus::Closed;
-        positions.set(position_id.clone(), position);
+        positions.set(position_id.clone(), position.clone());
         env.storage().instance().set(&DataKey::Positions, &positions);
 
-        env.events().publish(
-            (Symbol::new(&env, "PositionClosed"),),
-            position_id
+        Self::record_history(
+            &env,
+            &position_id,
+            HistoryAction::Close,
+            0,
+            position.owner.clone(),
+            position.last_oracle_round,
+        );
+
+        events::publish(
+            &env,
+            "PositionClosed",
+            position_id,
+            PositionClosedEvent { owner: position.owner },
         );
     }
 
@@ -329,6 +906,28 @@ impl Loans {
         positions.get(position_id)
     }
 
+    /// Get up to `limit` history entries for a position, oldest first, starting at `start`
+    pub fn get_position_history(
+        env: Env,
+        position_id: BytesN<32>,
+        start: u32,
+        limit: u32
+    ) -> Vec<HistoryEntry> {
+        let count = Self::history_counts(&env).get(position_id.clone()).unwrap_or(0);
+        let len = count.min(MAX_HISTORY_ENTRIES);
+        let oldest = count - len;
+
+        let mut history = Vec::new(&env);
+        let end = start.saturating_add(limit).min(len);
+        for index in start..end {
+            let slot = (oldest + index) % MAX_HISTORY_ENTRIES;
+            if let Some(entry) = env.storage().persistent().get(&DataKey::History(position_id.clone(), slot)) {
+                history.push_back(entry);
+            }
+        }
+        history
+    }
+
     /// Get total debt for a position (principal + accrued interest)
     pub fn get_total_debt(env: Env, position_id: BytesN<32>) -> i128 {
         if let Some(position) = Self::get_position(env, position_id) {
@@ -338,7 +937,44 @@ impl Loans {
         }
     }
 
-    /// Update position LTV (admin only, after price changes)
+    /// On-chain LTV of a position from its attested collateral and the current
+    /// oracle price. Debt with no attested collateral reports `i128::MAX`.
+    pub fn get_position_health(env: Env, position_id: BytesN<32>) -> PositionHealth {
+        let position = Self::get_position(env.clone(), position_id.clone()).expect("position not found");
+        let policy = Self::policy(&env, &position.asset);
+        Self::position_health(&env, &position_id, &position, &policy)
+    }
+
+    /// What `apply_liquidation` would do with `proceeds` and `collateral_sold`,
+    /// and the position's health afterwards at the current price (view function)
+    pub fn preview_liquidation(
+        env: Env,
+        position_id: BytesN<32>,
+        proceeds: i128,
+        collateral_sold: i128
+    ) -> (LiquidationOutcome, PositionHealth) {
+        if collateral_sold < 0 {
+            panic!("invalid amount");
+        }
+        let position = Self::get_position(env.clone(), position_id.clone()).expect("position not found");
+        let policy = Self::policy(&env, &position.asset);
+        let outcome = Self::liquidation_outcome(&position, proceeds);
+
+        let debt = outcome.principal + outcome.accrued_interest;
+        let collateral_value = match Self::get_attestation(env.clone(), position_id) {
+            Some(attestation) => {
+                let unsold = (attestation.amount - collateral_sold).max(0);
+                Self::collateral_value(&env, unsold, &position.asset, &policy)
+            }
+            None => 0,
+        };
+        let health = PositionHealth { debt, collateral_value, ltv_bps: Self::ltv_bps(debt, collateral_value) };
+
+        (outcome, health)
+    }
+
+    /// Update position LTV (admin only, after price changes). Unlike the
+    /// batch, this applies to any existing position, as it always has.
     pub fn update_ltv(
         env: Env,
         position_id: BytesN<32>,
@@ -351,13 +987,220 @@ impl Loans {
             .instance()
             .get(&DataKey::Positions)
             .unwrap_or(Map::new(&env));
-        
+
+        Self::apply_ltv(&env, &mut positions, &position_id, new_ltv_bps, oracle_round);
+
+        env.storage().instance().set(&DataKey::Positions, &positions);
+    }
+
+    /// Refresh the LTV of many positions against one oracle round (admin only).
+    /// `new_ltv_bps[i]` is applied to `position_ids[i]`; positions that are
+    /// missing or closed are skipped and reported instead of aborting the batch.
+    pub fn update_ltv_batch(
+        env: Env,
+        position_ids: Vec<BytesN<32>>,
+        new_ltv_bps: Vec<i128>,
+        oracle_round: i128
+    ) -> Vec<BatchOutcome> {
+        Self::require_admin(&env);
+        Self::check_batch(position_ids.len(), new_ltv_bps.len());
+
+        let mut positions: Map<BytesN<32>, Position> = env.storage()
+            .instance()
+            .get(&DataKey::Positions)
+            .unwrap_or(Map::new(&env));
+
+        let mut outcomes = Vec::new(&env);
+        for (position_id, ltv_bps) in position_ids.iter().zip(new_ltv_bps.iter()) {
+            let outcome = Self::batch_outcome(&positions, &position_id, ltv_bps >= 0);
+            if outcome == BatchOutcome::Applied {
+                Self::apply_ltv(&env, &mut positions, &position_id, ltv_bps, oracle_round);
+            }
+            outcomes.push_back(outcome);
+        }
+
+        env.storage().instance().set(&DataKey::Positions, &positions);
+        outcomes
+    }
+
+    fn policy(env: &Env, asset: &Symbol) -> Policy {
+        let policy_registry: Address = env.storage()
+            .instance()
+            .get(&DataKey::PolicyRegistry)
+            .expect("policy registry not set");
+        PolicyRegistryClient::new(env, &policy_registry)
+            .get_policy(asset)
+            .expect("policy not found")
+    }
+
+    /// Stablecoin value of a position's attested collateral at the current
+    /// oracle price, or 0 when nothing has been attested
+    fn attested_collateral_value(env: &Env, position_id: &BytesN<32>, asset: &Symbol, policy: &Policy) -> i128 {
+        match Self::get_attestation(env.clone(), position_id.clone()) {
+            Some(attestation) => Self::collateral_value(env, attestation.amount, asset, policy),
+            None => 0,
+        }
+    }
+
+    /// Stablecoin value of `amount` collateral units at the current oracle price
+    fn collateral_value(env: &Env, amount: i128, asset: &Symbol, policy: &Policy) -> i128 {
+        let price_adapter: Address = env.storage()
+            .instance()
+            .get(&DataKey::PriceAdapter)
+            .expect("price adapter not set");
+        let round = PriceAdapterClient::new(env, &price_adapter)
+            .get_price_if_fresh(asset, &(policy.staleness_seconds as u64))
+            .expect("price stale");
+
+        amount * round.price / PRICE_SCALE
+    }
+
+    /// Reduce debt by liquidation `proceeds`, interest first and then
+    /// principal. Proceeds beyond the debt are surplus. A position with debt
+    /// left enters its liquidation cooldown.
+    fn liquidation_outcome(position: &Position, proceeds: i128) -> LiquidationOutcome {
+        let interest_paid = proceeds.min(position.accrued_interest).max(0);
+        let principal_paid = (proceeds - interest_paid).min(position.principal).max(0);
+        let principal = position.principal - principal_paid;
+        let accrued_interest = position.accrued_interest - interest_paid;
+
+        let status = if principal == 0 && accrued_interest == 0 {
+            PositionStatus::Closed
+        } else {
+            PositionStatus::InLiquidationCooldown
+        };
+
+        LiquidationOutcome {
+            interest_paid,
+            principal_paid,
+            surplus: proceeds - interest_paid - principal_paid,
+            principal,
+            accrued_interest,
+            status,
+        }
+    }
+
+    /// Debt, attested collateral value and LTV of `position` as stored under
+    /// `position_id`; a debt with no attested collateral has an unbounded LTV
+    fn position_health(env: &Env, position_id: &BytesN<32>, position: &Position, policy: &Policy) -> PositionHealth {
+        let debt = position.principal + position.accrued_interest;
+        let collateral_value = Self::attested_collateral_value(env, position_id, &position.asset, policy);
+        PositionHealth { debt, collateral_value, ltv_bps: Self::ltv_bps(debt, collateral_value) }
+    }
+
+    fn ltv_bps(debt: i128, collateral_value: i128) -> i128 {
+        if debt == 0 {
+            0
+        } else if collateral_value == 0 {
+            i128::MAX
+        } else {
+            debt * BPS / collateral_value
+        }
+    }
+
+    fn cooldown_elapsed(env: &Env, position_id: &BytesN<32>, position: &Position) -> bool {
+        if position.status != PositionStatus::InLiquidationCooldown {
+            return false;
+        }
+        let cooldowns: Map<BytesN<32>, u64> = env.storage()
+            .instance()
+            .get(&DataKey::Cooldowns)
+            .unwrap_or(Map::new(env));
+        let started_at = cooldowns.get(position_id.clone()).unwrap_or(0);
+        let cooldown_seconds = Self::policy(env, &position.asset).cooldown_seconds as u64;
+        env.ledger().timestamp() >= started_at + cooldown_seconds
+    }
+
+    /// Flip a cooled-down position back to `Open`; the caller persists it
+    fn reopen(env: &Env, position_id: &BytesN<32>, position: &mut Position) {
+        position.status = PositionStatus::Open;
+
+        let mut cooldowns: Map<BytesN<32>, u64> = env.storage()
+            .instance()
+            .get(&DataKey::Cooldowns)
+            .unwrap_or(Map::new(env));
+        cooldowns.remove(position_id.clone());
+        env.storage().instance().set(&DataKey::Cooldowns, &cooldowns);
+
+        events::publish(
+            env,
+            "PositionReopened",
+            position_id.clone(),
+            PositionReopenedEvent { owner: position.owner.clone() },
+        );
+    }
+
+    fn check_batch(ids_len: u32, values_len: u32) {
+        if ids_len != values_len {
+            panic!("batch length mismatch");
+        }
+        if ids_len > MAX_BATCH_SIZE {
+            panic!("batch too large");
+        }
+    }
+
+    /// Whether a batch applies an update to `position_id`: it must exist, not
+    /// be closed, and carry a `valid` value
+    fn batch_outcome(positions: &Map<BytesN<32>, Position>, position_id: &BytesN<32>, valid: bool) -> BatchOutcome {
+        if !valid {
+            return BatchOutcome::InvalidAmount;
+        }
+        match positions.get(position_id.clone()) {
+            None => BatchOutcome::NotFound,
+            Some(position) if position.status == PositionStatus::Closed => BatchOutcome::Closed,
+            Some(_) => BatchOutcome::Applied,
+        }
+    }
+
+    /// Add interest to one position in `positions`; the caller persists the map.
+    fn apply_interest(
+        env: &Env,
+        positions: &mut Map<BytesN<32>, Position>,
+        position_id: &BytesN<32>,
+        interest_amount: i128,
+        admin: &Address
+    ) {
         let mut position = positions.get(position_id.clone()).expect("position not found");
+        position.accrued_interest += interest_amount;
+
+        let oracle_round = position.last_oracle_round;
+        positions.set(position_id.clone(), position);
+
+        Self::record_history(
+            env,
+            position_id,
+            HistoryAction::Interest,
+            interest_amount,
+            admin.clone(),
+            oracle_round,
+        );
+
+        events::publish(
+            env,
+            "InterestAccrued",
+            position_id.clone(),
+            InterestAccruedEvent { amount: interest_amount },
+        );
+    }
 
+    /// Set the LTV of one position in `positions`; the caller persists the map.
+    fn apply_ltv(
+        env: &Env,
+        positions: &mut Map<BytesN<32>, Position>,
+        position_id: &BytesN<32>,
+        new_ltv_bps: i128,
+        oracle_round: i128
+    ) {
+        let mut position = positions.get(position_id.clone()).expect("position not found");
         position.ltv_bps = new_ltv_bps;
         position.last_oracle_round = oracle_round;
-
         positions.set(position_id.clone(), position);
-        env.storage().instance().set(&DataKey::Positions, &positions);
+
+        events::publish(
+            env,
+            "LtvUpdated",
+            position_id.clone(),
+            LtvUpdatedEvent { ltv_bps: new_ltv_bps, oracle_round },
+        );
     }
-}
+}
\ No newline at end of file
@@ -0,0 +1,636 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use policy_registry::{PolicyRegistry, PolicyRegistryClient as RegistryClient};
+use price_adapter::{PriceAdapter, PriceAdapterClient as AdapterClient};
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{vec, IntoVal, Val};
+
+/// 60,000 stablecoin per unit, scaled by PRICE_SCALE
+const BTC_PRICE: i128 = 60_000 * PRICE_SCALE;
+
+/// Per-transaction Soroban network limits a batched call must stay within
+const TX_MAX_INSTRUCTIONS: i64 = 100_000_000;
+const TX_MAX_MEMORY_BYTES: i64 = 40 * 1024 * 1024;
+const TX_MAX_READ_BYTES: u32 = 200 * 1024;
+const TX_MAX_WRITE_ENTRIES: u32 = 25;
+const TX_MAX_WRITE_BYTES: u32 = 129 * 1024;
+const TX_MAX_EVENTS_BYTES: u32 = 16 * 1024;
+
+struct Setup {
+    env: Env,
+    loans: LoansClient<'static>,
+    adapter: AdapterClient<'static>,
+    custodian: SigningKey,
+    owner: Address,
+    asset: Symbol,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000_000);
+
+    let admin = Address::generate(&env);
+    let liquidation_manager = Address::generate(&env);
+
+    let registry = RegistryClient::new(&env, &env.register(PolicyRegistry, ()));
+    registry.initialize(&admin);
+    let asset = Symbol::new(&env, "BTC");
+    registry.set_policy(
+        &asset,
+        &7_000,
+        &vec![&env, 5_000i128, 6_000, 6_500],
+        &2_500,
+        &3_600,
+        &100,
+        &600,
+        &500,
+        &200,
+        &true,
+    );
+
+    let adapter = AdapterClient::new(&env, &env.register(PriceAdapter, ()));
+    adapter.initialize(&admin, &10_000);
+    adapter.update_price(&asset, &BTC_PRICE, &1, &Symbol::new(&env, "feed"));
+
+    let loans = LoansClient::new(&env, &env.register(Loans, ()));
+    loans.initialize(&admin, &registry.address, &adapter.address, &liquidation_manager);
+
+    let custodian = SigningKey::from_bytes(&[7u8; 32]);
+    loans.add_custodian(&BytesN::from_array(&env, &custodian.verifying_key().to_bytes()));
+
+    let owner = Address::generate(&env);
+    Setup { env, loans, adapter, custodian, owner, asset }
+}
+
+fn id(env: &Env, n: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[n; 32])
+}
+
+/// Custodian signature over an attestation as `contract` expects it
+fn sign(
+    s: &Setup,
+    contract: &Address,
+    position_id: &BytesN<32>,
+    vault_ref: &BytesN<32>,
+    amount: i128,
+    timestamp: u64
+) -> BytesN<64> {
+    let mut message = Bytes::from_slice(&s.env, ATTESTATION_DOMAIN);
+    message.append(&contract.clone().to_xdr(&s.env));
+    message.append(&position_id.clone().into());
+    message.append(&vault_ref.clone().into());
+    message.extend_from_array(&amount.to_be_bytes());
+    message.extend_from_array(&timestamp.to_be_bytes());
+
+    let mut buf = std::vec![0u8; message.len() as usize];
+    message.copy_into_slice(&mut buf);
+    BytesN::from_array(&s.env, &s.custodian.sign(&buf).to_bytes())
+}
+
+fn custodian_key(s: &Setup) -> BytesN<32> {
+    BytesN::from_array(&s.env, &s.custodian.verifying_key().to_bytes())
+}
+
+/// Open `position_id` against vault `vault_ref` and attest `amount` to it
+fn open_attested(s: &Setup, position_id: &BytesN<32>, vault_ref: &BytesN<32>, amount: i128) {
+    s.loans.open_position(position_id, &s.owner, vault_ref, &s.asset);
+    attest(s, position_id, vault_ref, amount);
+}
+
+fn attest(s: &Setup, position_id: &BytesN<32>, vault_ref: &BytesN<32>, amount: i128) {
+    let timestamp = s.env.ledger().timestamp();
+    let signature = sign(s, &s.loans.address, position_id, vault_ref, amount, timestamp);
+    s.loans.attest_collateral(position_id, &custodian_key(s), &amount, vault_ref, &timestamp, &signature);
+}
+
+#[test]
+#[should_panic(expected = "position already exists")]
+fn open_position_rejects_existing_id() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_attested(&s, &position_id, &id(&s.env, 10), 100);
+
+    // Someone else re-opening the id must not take over its attestation
+    let attacker = Address::generate(&s.env);
+    s.loans.open_position(&position_id, &attacker, &id(&s.env, 10), &s.asset);
+}
+
+#[test]
+fn attestation_is_bound_to_the_contract() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    let vault_ref = id(&s.env, 10);
+    open_attested(&s, &position_id, &vault_ref, 100);
+
+    let attestation = s.loans.get_attestation(&position_id).unwrap();
+    assert_eq!(attestation.amount, 100);
+    assert_eq!(attestation.vault_ref, vault_ref);
+}
+
+#[test]
+#[should_panic]
+fn attestation_for_another_deployment_is_rejected() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    let vault_ref = id(&s.env, 10);
+    s.loans.open_position(&position_id, &s.owner, &vault_ref, &s.asset);
+
+    let other_deployment = Address::generate(&s.env);
+    let timestamp = s.env.ledger().timestamp();
+    let signature = sign(&s, &other_deployment, &position_id, &vault_ref, 100, timestamp);
+    s.loans.attest_collateral(&position_id, &custodian_key(&s), &100, &vault_ref, &timestamp, &signature);
+}
+
+/// The event `name` as Loans publishes it for `position_id`
+fn event<D: IntoVal<Env, Val>>(s: &Setup, name: &str, position_id: &BytesN<32>, data: D) -> (Address, Vec<Val>, Val) {
+    (
+        s.loans.address.clone(),
+        (Symbol::new(&s.env, "loans"), Symbol::new(&s.env, name), EVENT_VERSION, position_id.clone()).into_val(&s.env),
+        data.into_val(&s.env),
+    )
+}
+
+#[test]
+fn open_and_attest_emit_their_events() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    let vault_ref = id(&s.env, 10);
+
+    s.loans.open_position(&position_id, &s.owner, &vault_ref, &s.asset);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "PositionOpened", &position_id, PositionOpenedEvent {
+                owner: s.owner.clone(),
+                asset: s.asset.clone(),
+                collateral_ref: vault_ref.clone(),
+            })
+        ]
+    );
+
+    attest(&s, &position_id, &vault_ref, 100);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "CollateralAttested", &position_id, CollateralAttestedEvent {
+                custodian: custodian_key(&s),
+                amount: 100,
+                vault_ref,
+                timestamp: 1_000_000,
+            })
+        ]
+    );
+}
+
+#[test]
+fn borrowing_entrypoints_emit_their_events() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_attested(&s, &position_id, &id(&s.env, 10), 1);
+
+    s.loans.draw(&position_id, &10_000, &1);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "Borrowed", &position_id, BorrowedEvent { owner: s.owner.clone(), amount: 10_000, oracle_round: 1 })
+        ]
+    );
+
+    s.loans.accrue_interest(&position_id, &50);
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "InterestAccrued", &position_id, InterestAccruedEvent { amount: 50 })]
+    );
+
+    s.loans.update_ltv(&position_id, &1_700, &2);
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "LtvUpdated", &position_id, LtvUpdatedEvent { ltv_bps: 1_700, oracle_round: 2 })]
+    );
+
+    s.loans.repay(&position_id, &s.owner, &4_000);
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "Repaid", &position_id, RepaidEvent { payer: s.owner.clone(), amount: 4_000 })]
+    );
+}
+
+#[test]
+fn apply_liquidation_emits_debt_reduced() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_attested(&s, &position_id, &id(&s.env, 10), 1);
+    s.loans.draw(&position_id, &10_000, &1);
+
+    s.loans.apply_liquidation(&position_id, &4_000, &0, &1, &1);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "DebtReduced", &position_id, DebtReducedEvent { proceeds: 4_000, oracle_round: 1, receipt_nonce: 1 })
+        ]
+    );
+
+    // Clearing the debt closes the position in the same call
+    s.loans.apply_liquidation(&position_id, &6_000, &0, &1, &1);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "PositionClosed", &position_id, PositionClosedEvent { owner: s.owner.clone() }),
+            event(&s, "DebtReduced", &position_id, DebtReducedEvent { proceeds: 6_000, oracle_round: 1, receipt_nonce: 1 })
+        ]
+    );
+}
+
+#[test]
+fn custodian_changes_emit_custodian_updated() {
+    let s = setup();
+    let custodian = BytesN::from_array(&s.env, &[9u8; 32]);
+    let topics = |name: &str| {
+        (Symbol::new(&s.env, "loans"), Symbol::new(&s.env, name), EVENT_VERSION, custodian.clone()).into_val(&s.env)
+    };
+
+    s.loans.add_custodian(&custodian);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            (s.loans.address.clone(), topics("CustodianUpdated"), CustodianUpdatedEvent { allowed: true }.into_val(&s.env))
+        ]
+    );
+
+    s.loans.remove_custodian(&custodian);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            (s.loans.address.clone(), topics("CustodianUpdated"), CustodianUpdatedEvent { allowed: false }.into_val(&s.env))
+        ]
+    );
+}
+
+/// Open `count` positions without collateral, returning their ids and a
+/// matching vec of `value`
+fn open_batch(s: &Setup, count: u32, value: i128) -> (Vec<BytesN<32>>, Vec<i128>) {
+    let mut ids = Vec::new(&s.env);
+    let mut values = Vec::new(&s.env);
+    for n in 0..count as u8 {
+        let position_id = id(&s.env, n + 1);
+        s.loans.open_position(&position_id, &s.owner, &id(&s.env, 100 + n), &s.asset);
+        ids.push_back(position_id);
+        values.push_back(value);
+    }
+    (ids, values)
+}
+
+fn assert_within_network_limits(s: &Setup) {
+    let resources = s.env.cost_estimate().resources();
+    assert!(resources.instructions <= TX_MAX_INSTRUCTIONS, "{:?}", resources);
+    assert!(resources.mem_bytes <= TX_MAX_MEMORY_BYTES, "{:?}", resources);
+    assert!(resources.read_bytes <= TX_MAX_READ_BYTES, "{:?}", resources);
+    assert!(resources.write_entries <= TX_MAX_WRITE_ENTRIES, "{:?}", resources);
+    assert!(resources.write_bytes <= TX_MAX_WRITE_BYTES, "{:?}", resources);
+    assert!(resources.contract_events_size_bytes <= TX_MAX_EVENTS_BYTES, "{:?}", resources);
+}
+
+#[test]
+fn full_interest_batch_fits_network_limits() {
+    let s = setup();
+    let (ids, amounts) = open_batch(&s, MAX_BATCH_SIZE, 1);
+
+    s.loans.accrue_interest_batch(&ids, &amounts);
+    assert_within_network_limits(&s);
+
+    // Once every history is full each entry overwrites its oldest slot
+    for _ in 0..MAX_HISTORY_ENTRIES {
+        s.loans.accrue_interest_batch(&ids, &amounts);
+    }
+    assert_within_network_limits(&s);
+    for position_id in ids.iter() {
+        assert_eq!(s.loans.get_position(&position_id).unwrap().accrued_interest, MAX_HISTORY_ENTRIES as i128 + 1);
+    }
+}
+
+#[test]
+fn full_ltv_batch_fits_network_limits() {
+    let s = setup();
+    let (ids, ltvs) = open_batch(&s, MAX_BATCH_SIZE, 4_000);
+
+    let outcomes = s.loans.update_ltv_batch(&ids, &ltvs, &2);
+    assert_within_network_limits(&s);
+    assert!(outcomes.iter().all(|outcome| outcome == BatchOutcome::Applied));
+    assert_eq!(s.loans.get_position(&ids.get(0).unwrap()).unwrap().ltv_bps, 4_000);
+}
+
+#[test]
+#[should_panic(expected = "batch too large")]
+fn oversized_batch_is_rejected() {
+    let s = setup();
+    let (ids, amounts) = open_batch(&s, MAX_BATCH_SIZE + 1, 1);
+    s.loans.accrue_interest_batch(&ids, &amounts);
+}
+
+#[test]
+fn batch_reports_skipped_positions() {
+    let s = setup();
+    let open = id(&s.env, 1);
+    let closed = id(&s.env, 2);
+    let missing = id(&s.env, 3);
+    s.loans.open_position(&open, &s.owner, &id(&s.env, 10), &s.asset);
+    open_attested(&s, &closed, &id(&s.env, 11), 1);
+    s.loans.draw(&closed, &1_000, &1);
+    s.loans.apply_liquidation(&closed, &1_000, &0, &1, &1);
+
+    let outcomes = s.loans.accrue_interest_batch(
+        &vec![&s.env, open.clone(), closed.clone(), missing, open.clone()],
+        &vec![&s.env, 10i128, 10, 10, 0],
+    );
+    assert!(
+        outcomes == vec![
+            &s.env,
+            BatchOutcome::Applied,
+            BatchOutcome::Closed,
+            BatchOutcome::NotFound,
+            BatchOutcome::InvalidAmount
+        ]
+    );
+    assert_eq!(s.loans.get_position(&open).unwrap().accrued_interest, 10);
+    assert_eq!(s.loans.get_position(&closed).unwrap().accrued_interest, 0);
+}
+
+#[test]
+fn single_updates_apply_where_the_batch_skips() {
+    let s = setup();
+    let closed = id(&s.env, 2);
+    open_attested(&s, &closed, &id(&s.env, 11), 1);
+    s.loans.draw(&closed, &1_000, &1);
+    s.loans.apply_liquidation(&closed, &1_000, &0, &1, &1);
+
+    // The single-position calls keep their original semantics: only a
+    // missing position is refused
+    s.loans.accrue_interest(&closed, &-5);
+    s.loans.update_ltv(&closed, &-1, &2);
+    let position = s.loans.get_position(&closed).unwrap();
+    assert_eq!((position.accrued_interest, position.ltv_bps), (-5, -1));
+    assert!(s.loans.try_accrue_interest(&id(&s.env, 3), &10).is_err());
+    assert!(s.loans.try_update_ltv(&id(&s.env, 3), &1_000, &2).is_err());
+}
+
+#[test]
+fn history_keeps_the_latest_entries_oldest_first() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    s.loans.open_position(&position_id, &s.owner, &id(&s.env, 10), &s.asset);
+    for amount in 1..=MAX_HISTORY_ENTRIES as i128 + 5 {
+        s.loans.accrue_interest(&position_id, &amount);
+    }
+
+    // The open entry and the first five accruals have been overwritten
+    let history = s.loans.get_position_history(&position_id, &0, &MAX_HISTORY_ENTRIES);
+    assert_eq!(history.len(), MAX_HISTORY_ENTRIES);
+    assert_eq!(history.get(0).unwrap().amount, 6);
+    assert_eq!(history.last().unwrap().amount, MAX_HISTORY_ENTRIES as i128 + 5);
+
+    let page = s.loans.get_position_history(&position_id, &98, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().amount, MAX_HISTORY_ENTRIES as i128 + 4);
+    assert!(s.loans.get_position_history(&position_id, &MAX_HISTORY_ENTRIES, &10).is_empty());
+}
+
+/// Position on vault `vault_ref` with 1,000 attested units (60,000,000) and
+/// 30,000,000 borrowed, i.e. 50% LTV
+fn half_drawn(s: &Setup, position_id: &BytesN<32>, vault_ref: &BytesN<32>) {
+    open_attested(s, position_id, vault_ref, 1_000);
+    s.loans.draw(position_id, &30_000_000, &1);
+}
+
+fn set_price(s: &Setup, price: i128, round_id: i128) {
+    s.adapter.update_price(&s.asset, &price, &round_id, &Symbol::new(&s.env, "feed"));
+}
+
+/// Split `collateral_share` and `debt_share` of `position_id` into `new_id`
+/// on `vault_ref`, with a custodian attestation of `amount` for it one second
+/// after the source's
+fn split(
+    s: &Setup,
+    position_id: &BytesN<32>,
+    new_id: &BytesN<32>,
+    vault_ref: &BytesN<32>,
+    amount: i128,
+    collateral_share: i128,
+    debt_share: i128
+) {
+    let timestamp = s.loans.get_attestation(position_id).unwrap().timestamp + 1;
+    s.env.ledger().set_timestamp(timestamp);
+    let attestation = Attestation { custodian: custodian_key(s), amount, vault_ref: vault_ref.clone(), timestamp };
+    let signature = sign(s, &s.loans.address, new_id, vault_ref, amount, timestamp);
+    s.loans.split_position(position_id, new_id, &collateral_share, &debt_share, &attestation, &signature);
+}
+
+#[test]
+fn split_moves_attested_collateral_and_prices_both_halves() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    let new_id = id(&s.env, 2);
+    half_drawn(&s, &position_id, &id(&s.env, 10));
+
+    split(&s, &position_id, &new_id, &id(&s.env, 10), 400, 4_000, 2_000);
+
+    // 24M of debt on 36M of collateral, and 6M on 24M
+    assert_eq!(s.loans.get_attestation(&position_id).unwrap().amount, 600);
+    assert_eq!(s.loans.get_attestation(&new_id).unwrap().amount, 400);
+    assert_eq!(s.loans.get_position_health(&position_id).ltv_bps, 6_666);
+    assert_eq!(s.loans.get_position(&position_id).unwrap().ltv_bps, 6_666);
+    assert_eq!(s.loans.get_position(&new_id).unwrap().ltv_bps, 2_500);
+}
+
+#[test]
+#[should_panic(expected = "ltv exceeds policy")]
+fn split_uses_the_current_price_not_the_stored_ltv() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    half_drawn(&s, &position_id, &id(&s.env, 10));
+
+    // At 45,000 the position is at 66% although its stored LTV still says 50%.
+    // Moving 60% of the debt with half the collateral would leave the new
+    // position at 80%.
+    set_price(&s, 45_000 * PRICE_SCALE, 2);
+    split(&s, &position_id, &id(&s.env, 2), &id(&s.env, 10), 500, 5_000, 6_000);
+}
+
+#[test]
+fn merge_pools_attested_collateral() {
+    let s = setup();
+    let a = id(&s.env, 1);
+    let b = id(&s.env, 2);
+    half_drawn(&s, &a, &id(&s.env, 10));
+    split(&s, &a, &b, &id(&s.env, 10), 500, 5_000, 5_000);
+
+    s.loans.merge_positions(&a, &b);
+
+    // Dated by the newer attestation, so `a`'s older one cannot be replayed
+    let attestation = s.loans.get_attestation(&a).unwrap();
+    assert_eq!(attestation.amount, 1_000);
+    assert_eq!(attestation.timestamp, 1_000_001);
+    assert!(s.loans.get_attestation(&b).is_none());
+    assert_eq!(s.loans.get_position(&a).unwrap().ltv_bps, 5_000);
+    assert!(s.loans.get_position(&b).unwrap().status == PositionStatus::Closed);
+}
+
+#[test]
+#[should_panic(expected = "ltv exceeds policy")]
+fn merge_uses_the_current_price_not_the_stored_ltv() {
+    let s = setup();
+    let a = id(&s.env, 1);
+    let b = id(&s.env, 2);
+    half_drawn(&s, &a, &id(&s.env, 10));
+    split(&s, &a, &b, &id(&s.env, 10), 500, 5_000, 5_000);
+
+    // 30M of debt on 40M of collateral is 75%
+    set_price(&s, 40_000 * PRICE_SCALE, 2);
+    s.loans.merge_positions(&a, &b);
+}
+
+#[test]
+#[should_panic(expected = "positions not compatible")]
+fn merge_rejects_positions_on_different_vaults() {
+    let s = setup();
+    let a = id(&s.env, 1);
+    let b = id(&s.env, 2);
+    half_drawn(&s, &a, &id(&s.env, 10));
+    half_drawn(&s, &b, &id(&s.env, 11));
+
+    s.loans.merge_positions(&a, &b);
+}
+
+#[test]
+fn split_moves_debt_to_another_vault() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    let new_id = id(&s.env, 2);
+    half_drawn(&s, &position_id, &id(&s.env, 10));
+
+    // 400 units leave vault 10 and the custodian attests them in vault 11
+    split(&s, &position_id, &new_id, &id(&s.env, 11), 400, 4_000, 2_000);
+
+    assert_eq!(s.loans.get_attestation(&position_id).unwrap().amount, 600);
+    let attestation = s.loans.get_attestation(&new_id).unwrap();
+    assert_eq!((attestation.amount, attestation.vault_ref), (400, id(&s.env, 11)));
+    assert_eq!(s.loans.get_position(&new_id).unwrap().collateral_ref, id(&s.env, 11));
+    assert_eq!(s.loans.get_position(&new_id).unwrap().ltv_bps, 2_500);
+}
+
+#[test]
+#[should_panic(expected = "attestation exceeds collateral moved")]
+fn split_on_one_vault_cannot_attest_more_than_it_moves() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    half_drawn(&s, &position_id, &id(&s.env, 10));
+    split(&s, &position_id, &id(&s.env, 2), &id(&s.env, 10), 401, 4_000, 2_000);
+}
+
+#[test]
+#[should_panic(expected = "stale attestation")]
+fn split_requires_a_fresh_attestation() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    let new_id = id(&s.env, 2);
+    let vault_ref = id(&s.env, 11);
+    half_drawn(&s, &position_id, &id(&s.env, 10));
+
+    // Signed at the same time as the source position's attestation
+    let timestamp = s.env.ledger().timestamp();
+    let attestation = Attestation { custodian: custodian_key(&s), amount: 400, vault_ref: vault_ref.clone(), timestamp };
+    let signature = sign(&s, &s.loans.address, &new_id, &vault_ref, 400, timestamp);
+    s.loans.split_position(&position_id, &new_id, &4_000, &2_000, &attestation, &signature);
+}
+
+#[test]
+fn draw_stores_the_ltv_it_checked() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    half_drawn(&s, &position_id, &id(&s.env, 10));
+    assert_eq!(s.loans.get_position(&position_id).unwrap().ltv_bps, 5_000);
+}
+
+#[test]
+#[should_panic(expected = "insufficient attested collateral")]
+fn sold_collateral_cannot_be_borrowed_against() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    half_drawn(&s, &position_id, &id(&s.env, 10));
+
+    // Selling 400 of the 1,000 units for 24,000,000 leaves 600 units
+    // (36,000,000) against 6,000,000 of debt
+    s.loans.apply_liquidation(&position_id, &24_000_000, &400, &1, &1);
+    assert_eq!(s.loans.get_attestation(&position_id).unwrap().amount, 600);
+
+    // 70% of the remaining 36,000,000 allows 25,200,000 of debt in total
+    s.env.ledger().set_timestamp(1_000_000 + 3_600);
+    set_price(&s, BTC_PRICE, 2);
+    s.loans.draw(&position_id, &19_200_001, &2);
+}
+
+#[test]
+fn split_and_merge_emit_their_events() {
+    let s = setup();
+    let a = id(&s.env, 1);
+    let b = id(&s.env, 2);
+    let vault_ref = id(&s.env, 10);
+    half_drawn(&s, &a, &vault_ref);
+
+    split(&s, &a, &b, &vault_ref, 400, 4_000, 2_000);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "CollateralAttested", &b, CollateralAttestedEvent {
+                custodian: custodian_key(&s),
+                amount: 400,
+                vault_ref,
+                timestamp: 1_000_001,
+            }),
+            event(&s, "PositionSplit", &a, PositionSplitEvent {
+                new_position_id: b.clone(),
+                collateral_share: 4_000,
+                debt_share: 2_000,
+                ltv_bps: 6_666,
+                new_ltv_bps: 2_500,
+            })
+        ]
+    );
+
+    s.loans.merge_positions(&a, &b);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "PositionsMerged", &a, PositionsMergedEvent { merged_position_id: b.clone(), ltv_bps: 5_000 }),
+            event(&s, "PositionClosed", &b, PositionClosedEvent { owner: s.owner.clone() })
+        ]
+    );
+}
+
+#[test]
+fn end_cooldown_emits_position_reopened() {
+    let s = setup();
+    let position_id = id(&s.env, 1);
+    open_attested(&s, &position_id, &id(&s.env, 10), 1);
+    s.loans.draw(&position_id, &10_000, &1);
+    s.loans.apply_liquidation(&position_id, &4_000, &0, &1, &1);
+
+    s.env.ledger().set_timestamp(1_000_000 + 3_600);
+    assert!(s.loans.end_cooldown(&position_id));
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "PositionReopened", &position_id, PositionReopenedEvent { owner: s.owner.clone() })]
+    );
+}
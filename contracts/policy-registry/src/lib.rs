@@ -1,5 +1,12 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Map};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, String, Map};
+
+mod events;
+
+#[cfg(test)]
+mod test;
+
+pub use events::*;
 
 // Policy Registry for Legasi protocol
 // Stores all risk parameters and configuration
@@ -12,17 +19,27 @@ pub enum DataKey {
     AllParameters,
 }
 
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAuthorized = 3,
+    ParameterNotFound = 4,
+}
+
 #[contract]
 pub struct PolicyRegistryContract;
 
 #[contractimpl]
 impl PolicyRegistryContract {
     /// Initialize the contract
-    pub fn initialize(env: Env, admin: Address) -> Result<(), String> {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
         admin.require_auth();
 
         if env.storage().instance().has(&DataKey::Admin) {
-            return Err(String::from_str(&env, "Already initialized"));
+            return Err(Error::AlreadyInitialized);
         }
 
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -39,17 +56,17 @@ impl PolicyRegistryContract {
         admin: Address,
         name: String,
         value: i128,
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         admin.require_auth();
 
         let stored_admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .ok_or(String::from_str(&env, "Not initialized"))?;
+            .ok_or(Error::NotInitialized)?;
 
         if admin != stored_admin {
-            return Err(String::from_str(&env, "Not authorized"));
+            return Err(Error::NotAuthorized);
         }
 
         env.storage()
@@ -63,20 +80,22 @@ impl PolicyRegistryContract {
             .get(&DataKey::AllParameters)
             .unwrap_or(Map::new(&env));
 
-        all_params.set(name, value);
+        all_params.set(name.clone(), value);
         env.storage()
             .persistent()
             .set(&DataKey::AllParameters, &all_params);
 
+        events::publish(&env, "ParameterUpdated", name, ParameterUpdatedEvent { value });
+
         Ok(())
     }
 
     /// Get a policy parameter
-    pub fn get_parameter(env: Env, name: String) -> Result<i128, String> {
+    pub fn get_parameter(env: Env, name: String) -> Result<i128, Error> {
         env.storage()
             .persistent()
             .get(&DataKey::Parameter(name))
-            .ok_or(String::from_str(&env, "Parameter not found"))
+            .ok_or(Error::ParameterNotFound)
     }
 
     /// Get all parameters
@@ -109,11 +128,6 @@ impl PolicyRegistryContract {
         all_params.set(String::from_str(env, "base_interest_rate"), 550);
 
         // Liquidation parameters
-        env.storage()
-            .persistent()
-            .set(&DataKey::Parameter(String::from_str(env, "liquidation_penalty")), &500i128); // 5%
-        all_params.set(String::from_str(env, "liquidation_penalty"), 500);
-
         env.storage()
             .persistent()
             .set(&DataKey::Parameter(String::from_str(env, "soft_liquidation_step")), &1000i128); // 10%
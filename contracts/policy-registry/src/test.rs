@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Events};
+use soroban_sdk::{vec, IntoVal, Symbol, Val};
+
+struct Setup {
+    env: Env,
+    registry: PolicyRegistryContractClient<'static>,
+    admin: Address,
+}
+
+/// Registry initialized with its default parameters
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let registry = PolicyRegistryContractClient::new(&env, &env.register_contract(None, PolicyRegistryContract));
+    let admin = Address::generate(&env);
+    registry.initialize(&admin);
+
+    Setup { env, registry, admin }
+}
+
+/// The event `name` as the registry publishes it
+fn event<I, D>(s: &Setup, name: &str, id: I, data: D) -> (Address, soroban_sdk::Vec<Val>, Val)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    (
+        s.registry.address.clone(),
+        (Symbol::new(&s.env, "policy_registry_contract"), Symbol::new(&s.env, name), EVENT_VERSION, id)
+            .into_val(&s.env),
+        data.into_val(&s.env),
+    )
+}
+
+#[test]
+fn set_parameter_emits_parameter_updated() {
+    let s = setup();
+    let name = String::from_str(&s.env, "btc_ltv_threshold");
+    s.registry.set_parameter(&s.admin, &name, &7_000);
+
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "ParameterUpdated", name.clone(), ParameterUpdatedEvent { value: 7_000 })]
+    );
+    assert_eq!(s.registry.get_parameter(&name), 7_000);
+    assert_eq!(s.registry.get_all_parameters().get(name), Some(7_000));
+}
+
+#[test]
+fn errors_are_reported_as_contract_errors() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    let name = String::from_str(&s.env, "btc_ltv_threshold");
+
+    assert_eq!(s.registry.try_set_parameter(&stranger, &name, &1), Err(Ok(Error::NotAuthorized)));
+    assert_eq!(s.registry.try_initialize(&stranger), Err(Ok(Error::AlreadyInitialized)));
+    assert_eq!(
+        s.registry.try_get_parameter(&String::from_str(&s.env, "unknown")),
+        Err(Ok(Error::ParameterNotFound))
+    );
+    assert_eq!(s.registry.get_parameter(&name), 7_500);
+}
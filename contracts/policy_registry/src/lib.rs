@@ -1,8 +1,19 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, Vec, Map, Bytes};
 
+mod events;
+mod timelock;
+
+#[cfg(test)]
+mod test;
+
+pub use events::*;
+pub use timelock::{PolicyChange, QueuedPolicy};
+
+const BPS: i128 = 10_000;
+
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Policy {
     pub max_ltv_bps: i128,
     pub bands: Vec<i128>,
@@ -16,6 +27,15 @@ pub struct Policy {
     pub circuit_breaker: bool,
 }
 
+/// Share of liquidation proceeds taken as a penalty, and the keeper's cut
+/// of that penalty
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LiquidationPenalty {
+    pub penalty_bps: i128,
+    pub keeper_bounty_bps: i128,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
@@ -23,6 +43,14 @@ pub enum DataKey {
     Policies,
     VenueAllowlist,
     Version,
+    Guardian,
+    PolicyDelay,
+    QueuedPolicies,
+    NextProposalId,
+    PolicyAt(Symbol, i128),
+    PolicyVersions(Symbol),
+    DisputeWindow(Symbol),
+    LiquidationPenalty(Symbol),
 }
 
 #[contract]
@@ -43,6 +71,8 @@ impl PolicyRegistry {
         admin.require_auth();
     }
 
+    /// List a new asset with its initial policy, effective immediately.
+    /// Changes to an existing policy go through `queue_policy`.
     pub fn set_policy(
         env: Env,
         asset: Symbol,
@@ -58,6 +88,10 @@ impl PolicyRegistry {
     ) {
         Self::require_admin(&env);
         
+        if Self::get_policy(env.clone(), asset.clone()).is_some() {
+            panic!("policy exists, use queue_policy");
+        }
+        
         let policy = Policy {
             max_ltv_bps,
             bands,
@@ -71,20 +105,72 @@ impl PolicyRegistry {
             circuit_breaker: false,
         };
         
+        Self::store_policy(&env, asset, policy);
+    }
+
+    /// Write a policy and bump the global version. The circuit breaker is set
+    /// separately and survives policy updates.
+    fn store_policy(env: &Env, asset: Symbol, mut policy: Policy) {
         let mut policies: Map<Symbol, Policy> = env.storage()
             .instance()
             .get(&DataKey::Policies)
-            .unwrap_or(Map::new(&env));
+            .unwrap_or(Map::new(env));
+        
+        if let Some(current) = policies.get(asset.clone()) {
+            policy.circuit_breaker = current.circuit_breaker;
+        }
         
-        policies.set(asset.clone(), policy);
+        policies.set(asset.clone(), policy.clone());
         env.storage().instance().set(&DataKey::Policies, &policies);
 
-        // Bump version
+        Self::record_version(env, asset, policy);
+    }
+
+    /// Bump the global version and keep an immutable snapshot of the asset's
+    /// policy under it
+    fn record_version(env: &Env, asset: Symbol, policy: Policy) {
         let mut version: i128 = env.storage().instance().get(&DataKey::Version).unwrap_or(0i128);
         version += 1;
         env.storage().instance().set(&DataKey::Version, &version);
         
-        env.events().publish((Symbol::new(&env, "PolicyUpdated"), asset), version);
+        env.storage().persistent().set(&DataKey::PolicyAt(asset.clone(), version), &policy);
+        let versions_key = DataKey::PolicyVersions(asset.clone());
+        let mut versions: Vec<i128> = env.storage()
+            .persistent()
+            .get(&versions_key)
+            .unwrap_or(Vec::new(env));
+        versions.push_back(version);
+        env.storage().persistent().set(&versions_key, &versions);
+        
+        events::publish(env, "PolicyUpdated", asset, PolicyUpdatedEvent { version });
+    }
+
+    /// Policy of `asset` in force at global `version`, i.e. its latest
+    /// snapshot at or before that version. Circuit breaker toggles are not
+    /// versioned; a snapshot carries the breaker state from when it was taken.
+    pub fn get_policy_at(env: Env, asset: Symbol, version: i128) -> Option<Policy> {
+        let versions: Vec<i128> = env.storage()
+            .persistent()
+            .get(&DataKey::PolicyVersions(asset.clone()))
+            .unwrap_or(Vec::new(&env));
+        
+        let mut in_force = None;
+        for snapshot_version in versions.iter() {
+            if snapshot_version > version {
+                break;
+            }
+            in_force = Some(snapshot_version);
+        }
+        
+        in_force.and_then(|v| env.storage().persistent().get(&DataKey::PolicyAt(asset, v)))
+    }
+
+    /// Versions at which `asset`'s policy changed, oldest first
+    pub fn get_policy_versions(env: Env, asset: Symbol) -> Vec<i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PolicyVersions(asset))
+            .unwrap_or(Vec::new(&env))
     }
 
     pub fn get_policy(env: Env, asset: Symbol) -> Option<Policy> {
@@ -95,16 +181,17 @@ impl PolicyRegistry {
         policies.get(asset)
     }
 
-    pub fn add_venue(env: Env, venue_hash: Bytes) {
-        Self::require_admin(&env);
-        
+    /// Add a venue to the allowlist. Set through `queue_venue`.
+    fn store_venue(env: &Env, venue_hash: Bytes) {
         let mut venues: Vec<Bytes> = env.storage()
             .instance()
             .get(&DataKey::VenueAllowlist)
-            .unwrap_or(Vec::new(&env));
+            .unwrap_or(Vec::new(env));
         
-        venues.push_back(venue_hash);
+        venues.push_back(venue_hash.clone());
         env.storage().instance().set(&DataKey::VenueAllowlist, &venues);
+
+        events::publish(env, "VenueUpdated", venue_hash, VenueUpdatedEvent { allowed: true });
     }
 
     pub fn remove_venue(env: Env, venue_hash: Bytes) {
@@ -122,6 +209,8 @@ impl PolicyRegistry {
             }
         }
         env.storage().instance().set(&DataKey::VenueAllowlist, &new_venues);
+
+        events::publish(&env, "VenueUpdated", venue_hash, VenueUpdatedEvent { allowed: false });
     }
 
     pub fn is_venue_allowed(env: Env, venue_hash: Bytes) -> bool {
@@ -138,25 +227,82 @@ impl PolicyRegistry {
         false
     }
 
+    /// Trip an asset's circuit breaker (admin only). Clearing it loosens
+    /// the policy and goes through `queue_circuit_breaker_reset`.
     pub fn toggle_circuit_breaker(env: Env, asset: Symbol, enabled: bool) {
         Self::require_admin(&env);
         
+        if !enabled {
+            panic!("use queue_circuit_breaker_reset");
+        }
+        Self::set_circuit_breaker(&env, asset, enabled);
+    }
+
+    fn set_circuit_breaker(env: &Env, asset: Symbol, enabled: bool) {
         let mut policies: Map<Symbol, Policy> = env.storage()
             .instance()
             .get(&DataKey::Policies)
-            .unwrap_or(Map::new(&env));
+            .unwrap_or(Map::new(env));
         
         if let Some(mut policy) = policies.get(asset.clone()) {
             policy.circuit_breaker = enabled;
             policies.set(asset.clone(), policy);
             env.storage().instance().set(&DataKey::Policies, &policies);
             
-            env.events().publish((Symbol::new(&env, "CircuitBreaker"), asset), enabled);
+            events::publish(env, "CircuitBreaker", asset, CircuitBreakerEvent { enabled });
         } else {
             panic!("policy not found");
         }
     }
 
+    /// Hold liquidation receipts for `dispute_window_seconds` before they are
+    /// applied, so a guardian or the borrower can challenge them. Set through
+    /// `queue_dispute_window` and kept outside `Policy` so policies stored
+    /// before disputes existed still decode.
+    fn store_dispute_window(env: &Env, asset: Symbol, dispute_window_seconds: i128) {
+        env.storage().instance().set(&DataKey::DisputeWindow(asset.clone()), &dispute_window_seconds);
+        
+        events::publish(
+            env,
+            "DisputeWindowUpdated",
+            asset,
+            DisputeWindowUpdatedEvent { dispute_window_seconds },
+        );
+    }
+
+    /// Seconds liquidation receipts for `asset` are held for challenges;
+    /// 0 means they settle immediately
+    pub fn get_dispute_window(env: Env, asset: Symbol) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::DisputeWindow(asset))
+            .unwrap_or(0i128)
+    }
+
+    /// Set through `queue_liquidation_penalty`
+    fn store_liquidation_penalty(env: &Env, asset: Symbol, penalty: LiquidationPenalty) {
+        env.storage().instance().set(&DataKey::LiquidationPenalty(asset.clone()), &penalty);
+        
+        events::publish(
+            env,
+            "LiquidationPenaltyUpdated",
+            asset,
+            LiquidationPenaltyUpdatedEvent {
+                penalty_bps: penalty.penalty_bps,
+                keeper_bounty_bps: penalty.keeper_bounty_bps,
+            },
+        );
+    }
+
+    /// Penalty charged on `asset`'s liquidation proceeds; none until one has
+    /// been queued and executed
+    pub fn get_liquidation_penalty(env: Env, asset: Symbol) -> LiquidationPenalty {
+        env.storage()
+            .instance()
+            .get(&DataKey::LiquidationPenalty(asset))
+            .unwrap_or(LiquidationPenalty { penalty_bps: 0, keeper_bounty_bps: 0 })
+    }
+
     pub fn get_version(env: Env) -> i128 {
         env.storage().instance().get(&DataKey::Version).unwrap_or(0i128)
     }
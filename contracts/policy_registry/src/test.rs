@@ -0,0 +1,334 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{vec, IntoVal, Val};
+
+struct Setup {
+    env: Env,
+    registry: PolicyRegistryClient<'static>,
+    guardian: Address,
+    asset: Symbol,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000_000);
+
+    let registry = PolicyRegistryClient::new(&env, &env.register(PolicyRegistry, ()));
+    registry.initialize(&Address::generate(&env));
+    let guardian = Address::generate(&env);
+    registry.set_guardian(&guardian);
+
+    let asset = Symbol::new(&env, "BTC");
+    Setup { env, registry, guardian, asset }
+}
+
+/// Execute a queued change it once the policy delay has passed
+fn execute(s: &Setup, proposal_id: u64) {
+    let eta = s.registry.get_queued_policy(&proposal_id).unwrap().eta;
+    s.env.ledger().set_timestamp(eta);
+    s.registry.execute_policy(&proposal_id);
+}
+
+fn list_asset(s: &Setup) {
+    s.registry.set_policy(
+        &s.asset,
+        &7_000,
+        &vec![&s.env, 5_000i128, 6_000, 6_500],
+        &2_500,
+        &3_600,
+        &100,
+        &600,
+        &500,
+        &200,
+        &true,
+    );
+}
+
+/// The event `name` as this registry publishes it
+fn event<I, D>(s: &Setup, name: &str, id: I, data: D) -> (Address, soroban_sdk::Vec<Val>, Val)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    (
+        s.registry.address.clone(),
+        (Symbol::new(&s.env, "policy_registry"), Symbol::new(&s.env, name), EVENT_VERSION, id).into_val(&s.env),
+        data.into_val(&s.env),
+    )
+}
+
+#[test]
+fn set_policy_emits_policy_updated() {
+    let s = setup();
+    list_asset(&s);
+
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "PolicyUpdated", s.asset.clone(), PolicyUpdatedEvent { version: 1 })]
+    );
+}
+
+#[test]
+fn venue_changes_emit_venue_updated() {
+    let s = setup();
+    let venue = Bytes::from_slice(&s.env, b"venue");
+
+    let proposal_id = s.registry.queue_venue(&venue);
+    assert!(!s.registry.is_venue_allowed(&venue));
+    execute(&s, proposal_id);
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "VenueUpdated", venue.clone(), VenueUpdatedEvent { allowed: true })]
+    );
+    assert!(s.registry.is_venue_allowed(&venue));
+
+    s.registry.remove_venue(&venue);
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "VenueUpdated", venue, VenueUpdatedEvent { allowed: false })]
+    );
+}
+
+#[test]
+fn circuit_breaker_changes_emit_circuit_breaker() {
+    let s = setup();
+    list_asset(&s);
+
+    s.registry.emergency_halt(&s.asset);
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "CircuitBreaker", s.asset.clone(), CircuitBreakerEvent { enabled: true })]
+    );
+
+    let proposal_id = s.registry.queue_circuit_breaker_reset(&s.asset);
+    assert!(s.registry.get_policy(&s.asset).unwrap().circuit_breaker);
+    execute(&s, proposal_id);
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "CircuitBreaker", s.asset.clone(), CircuitBreakerEvent { enabled: false })]
+    );
+}
+
+#[test]
+fn timelocked_policy_emits_queued_then_updated() {
+    let s = setup();
+    list_asset(&s);
+    let policy = s.registry.get_policy(&s.asset).unwrap();
+
+    let eta = 1_000_000 + s.registry.get_policy_delay();
+    let policy = Policy { max_ltv_bps: 6_500, ..policy };
+    let proposal_id = s.registry.queue_policy(&s.asset, &policy);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "PolicyQueued", proposal_id, PolicyQueuedEvent { change: PolicyChange::Policy(s.asset.clone(), policy), eta })
+        ]
+    );
+
+    s.env.ledger().set_timestamp(eta);
+    s.registry.execute_policy(&proposal_id);
+    assert_eq!(
+        s.env.events().all(),
+        vec![&s.env, event(&s, "PolicyUpdated", s.asset.clone(), PolicyUpdatedEvent { version: 2 })]
+    );
+}
+
+#[test]
+fn cancel_policy_emits_policy_cancelled() {
+    let s = setup();
+    list_asset(&s);
+    let policy = s.registry.get_policy(&s.asset).unwrap();
+    let proposal_id = s.registry.queue_policy(&s.asset, &policy);
+
+    s.registry.cancel_policy(&s.guardian, &proposal_id);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "PolicyCancelled", proposal_id, PolicyCancelledEvent { change: PolicyChange::Policy(s.asset.clone(), policy) })
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "timelock not elapsed")]
+fn execute_policy_before_eta_panics() {
+    let s = setup();
+    list_asset(&s);
+    let policy = s.registry.get_policy(&s.asset).unwrap();
+    let proposal_id = s.registry.queue_policy(&s.asset, &policy);
+
+    let eta = s.registry.get_queued_policy(&proposal_id).unwrap().eta;
+    s.env.ledger().set_timestamp(eta - 1);
+    s.registry.execute_policy(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "proposal not found")]
+fn cancelled_policy_cannot_execute() {
+    let s = setup();
+    list_asset(&s);
+    let policy = s.registry.get_policy(&s.asset).unwrap();
+    let proposal_id = s.registry.queue_policy(&s.asset, &policy);
+
+    s.registry.cancel_policy(&s.guardian, &proposal_id);
+    s.env.ledger().set_timestamp(1_000_000 + s.registry.get_policy_delay());
+    s.registry.execute_policy(&proposal_id);
+}
+
+#[test]
+#[should_panic(expected = "policy exists, use queue_policy")]
+fn set_policy_on_listed_asset_panics() {
+    let s = setup();
+    list_asset(&s);
+    list_asset(&s);
+}
+
+#[test]
+#[should_panic(expected = "delay can only increase")]
+fn policy_delay_cannot_decrease() {
+    let s = setup();
+    let delay = s.registry.get_policy_delay();
+    s.registry.set_policy_delay(&(delay * 2));
+    s.registry.set_policy_delay(&(delay * 2 - 1));
+}
+
+#[test]
+#[should_panic(expected = "delay can only increase")]
+fn policy_delay_cannot_go_below_minimum() {
+    let s = setup();
+    s.registry.set_policy_delay(&(s.registry.get_policy_delay() - 1));
+}
+
+#[test]
+#[should_panic(expected = "use queue_circuit_breaker_reset")]
+fn circuit_breaker_cannot_be_cleared_instantly() {
+    let s = setup();
+    list_asset(&s);
+    s.registry.toggle_circuit_breaker(&s.asset, &true);
+    s.registry.toggle_circuit_breaker(&s.asset, &false);
+}
+
+#[test]
+fn guardian_hands_over_its_own_role() {
+    let s = setup();
+    let next = Address::generate(&s.env);
+
+    s.registry.set_guardian(&next);
+    assert_eq!(s.env.auths()[0].0, s.guardian);
+    assert_eq!(s.registry.get_guardian(), Some(next));
+}
+
+#[test]
+fn liquidation_penalty_is_timelocked() {
+    let s = setup();
+    list_asset(&s);
+    let penalty = LiquidationPenalty { penalty_bps: 500, keeper_bounty_bps: 5_000 };
+
+    let proposal_id = s.registry.queue_liquidation_penalty(&s.asset, &penalty);
+    assert_eq!(s.registry.get_liquidation_penalty(&s.asset).penalty_bps, 0);
+
+    execute(&s, proposal_id);
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(
+                &s,
+                "LiquidationPenaltyUpdated",
+                s.asset.clone(),
+                LiquidationPenaltyUpdatedEvent { penalty_bps: 500, keeper_bounty_bps: 5_000 }
+            )
+        ]
+    );
+    assert_eq!(s.registry.get_liquidation_penalty(&s.asset), penalty);
+}
+
+#[test]
+#[should_panic(expected = "invalid bps")]
+fn full_liquidation_penalty_is_rejected() {
+    let s = setup();
+    list_asset(&s);
+    s.registry.queue_liquidation_penalty(&s.asset, &LiquidationPenalty { penalty_bps: 10_000, keeper_bounty_bps: 0 });
+}
+
+#[test]
+fn dispute_window_change_emits_dispute_window_updated() {
+    let s = setup();
+    list_asset(&s);
+
+    let proposal_id = s.registry.queue_dispute_window(&s.asset, &3_600);
+    s.env.ledger().set_timestamp(1_000_000 + s.registry.get_policy_delay());
+    s.registry.execute_policy(&proposal_id);
+
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "DisputeWindowUpdated", s.asset.clone(), DisputeWindowUpdatedEvent { dispute_window_seconds: 3_600 })
+        ]
+    );
+    assert_eq!(s.registry.get_dispute_window(&s.asset), 3_600);
+}
+
+/// The listed BTC policy with a different maximum LTV
+fn with_max_ltv(s: &Setup, max_ltv_bps: i128) -> Policy {
+    Policy { max_ltv_bps, ..s.registry.get_policy(&s.asset).unwrap() }
+}
+
+#[test]
+fn get_policy_at_returns_the_snapshot_in_force_between_versions() {
+    let s = setup();
+    list_asset(&s);
+    let listed = s.registry.get_policy(&s.asset).unwrap();
+
+    // Listing another asset moves the global version without a BTC snapshot
+    let eth = Symbol::new(&s.env, "ETH");
+    s.registry.set_policy(&eth, &6_000, &vec![&s.env, 5_000i128], &2_500, &3_600, &100, &600, &500, &200, &true);
+    let proposal_id = s.registry.queue_policy(&s.asset, &with_max_ltv(&s, 6_500));
+    execute(&s, proposal_id);
+
+    assert_eq!(s.registry.get_version(), 3);
+    assert_eq!(s.registry.get_policy_versions(&s.asset), vec![&s.env, 1i128, 3]);
+    assert_eq!(s.registry.get_policy_at(&s.asset, &1), Some(listed.clone()));
+    assert_eq!(s.registry.get_policy_at(&s.asset, &2), Some(listed));
+    assert_eq!(s.registry.get_policy_at(&s.asset, &3).unwrap().max_ltv_bps, 6_500);
+    assert_eq!(s.registry.get_policy_at(&s.asset, &10).unwrap().max_ltv_bps, 6_500);
+}
+
+#[test]
+fn get_policy_at_is_none_before_the_first_snapshot() {
+    let s = setup();
+    let eth = Symbol::new(&s.env, "ETH");
+    s.registry.set_policy(&eth, &6_000, &vec![&s.env, 5_000i128], &2_500, &3_600, &100, &600, &500, &200, &true);
+    list_asset(&s);
+
+    assert_eq!(s.registry.get_policy_at(&s.asset, &0), None);
+    assert_eq!(s.registry.get_policy_at(&s.asset, &1), None);
+    assert!(s.registry.get_policy_at(&s.asset, &2).is_some());
+    assert_eq!(s.registry.get_policy_at(&Symbol::new(&s.env, "SOL"), &2), None);
+}
+
+#[test]
+fn get_policy_at_follows_a_timelocked_execute_policy() {
+    let s = setup();
+    list_asset(&s);
+    let listed = s.registry.get_policy(&s.asset).unwrap();
+
+    // Queueing alone neither bumps the version nor takes a snapshot
+    let proposal_id = s.registry.queue_policy(&s.asset, &with_max_ltv(&s, 6_500));
+    assert_eq!(s.registry.get_version(), 1);
+    assert_eq!(s.registry.get_policy_at(&s.asset, &2), Some(listed.clone()));
+
+    execute(&s, proposal_id);
+    let version = s.registry.get_version();
+    assert_eq!(version, 2);
+    assert_eq!(s.registry.get_policy_at(&s.asset, &version), s.registry.get_policy(&s.asset));
+    assert_eq!(s.registry.get_policy_at(&s.asset, &version).unwrap().max_ltv_bps, 6_500);
+    assert_eq!(s.registry.get_policy_at(&s.asset, &1), Some(listed));
+}
@@ -0,0 +1,191 @@
+use soroban_sdk::{contractimpl, contracttype, Address, Bytes, Env, Map, Symbol};
+
+use crate::*;
+
+// Timelocked policy changes. Anything that loosens the rules (a listed
+// asset's policy, dispute window or liquidation penalty, a new venue, or
+// clearing a circuit breaker) is queued with an earliest execution time and
+// only takes effect once the delay has passed, giving the guardian a window
+// to cancel it. Tightening in an emergency (tripping a circuit breaker,
+// removing a venue) stays instant.
+
+/// Floor on the timelock delay, whatever has been configured
+const MIN_POLICY_DELAY: u64 = 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PolicyChange {
+    Policy(Symbol, Policy),
+    DisputeWindow(Symbol, i128),
+    LiquidationPenalty(Symbol, LiquidationPenalty),
+    ResetCircuitBreaker(Symbol),
+    AddVenue(Bytes),
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct QueuedPolicy {
+    pub change: PolicyChange,
+    pub eta: u64,
+}
+
+#[contractimpl]
+impl PolicyRegistry {
+    /// Set the first guardian (admin only). After that only the current
+    /// guardian can hand the role over, so the admin cannot swap in a
+    /// guardian that will not cancel its queued changes.
+    pub fn set_guardian(env: Env, guardian: Address) {
+        match Self::get_guardian(env.clone()) {
+            Some(current) => current.require_auth(),
+            None => Self::require_admin(&env),
+        }
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+    }
+
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Guardian)
+    }
+
+    /// Set the delay between queueing and executing a policy change (admin
+    /// only). The delay can only be increased and is never below one day.
+    pub fn set_policy_delay(env: Env, delay_seconds: u64) {
+        Self::require_admin(&env);
+        
+        if delay_seconds < Self::get_policy_delay(env.clone()) {
+            panic!("delay can only increase");
+        }
+        env.storage().instance().set(&DataKey::PolicyDelay, &delay_seconds);
+    }
+
+    pub fn get_policy_delay(env: Env) -> u64 {
+        let delay: u64 = env.storage().instance().get(&DataKey::PolicyDelay).unwrap_or(0u64);
+        delay.max(MIN_POLICY_DELAY)
+    }
+
+    /// Queue a policy for an asset (admin only). It can be executed once the
+    /// policy delay has passed. The circuit breaker is toggled separately, so
+    /// `policy.circuit_breaker` is ignored. Returns the proposal id.
+    pub fn queue_policy(env: Env, asset: Symbol, policy: Policy) -> u64 {
+        Self::require_admin(&env);
+        
+        let policy = Policy { circuit_breaker: false, ..policy };
+        Self::queue_change(&env, PolicyChange::Policy(asset, policy))
+    }
+
+    /// Queue a new dispute window for a listed asset's liquidation receipts
+    /// (admin only); see `get_dispute_window`. Returns the proposal id.
+    pub fn queue_dispute_window(env: Env, asset: Symbol, dispute_window_seconds: i128) -> u64 {
+        Self::require_admin(&env);
+        
+        if dispute_window_seconds < 0 {
+            panic!("invalid dispute window");
+        }
+        Self::require_listed(&env, &asset);
+        Self::queue_change(&env, PolicyChange::DisputeWindow(asset, dispute_window_seconds))
+    }
+
+    /// Queue a new liquidation penalty for a listed asset (admin only); see
+    /// `get_liquidation_penalty`. Returns the proposal id.
+    pub fn queue_liquidation_penalty(env: Env, asset: Symbol, penalty: LiquidationPenalty) -> u64 {
+        Self::require_admin(&env);
+        
+        // A penalty of 100% would leave nothing to credit to the loan
+        if !(0..BPS).contains(&penalty.penalty_bps) || !(0..=BPS).contains(&penalty.keeper_bounty_bps) {
+            panic!("invalid bps");
+        }
+        Self::require_listed(&env, &asset);
+        Self::queue_change(&env, PolicyChange::LiquidationPenalty(asset, penalty))
+    }
+
+    /// Queue clearing a listed asset's circuit breaker (admin only). Returns
+    /// the proposal id.
+    pub fn queue_circuit_breaker_reset(env: Env, asset: Symbol) -> u64 {
+        Self::require_admin(&env);
+        
+        Self::require_listed(&env, &asset);
+        Self::queue_change(&env, PolicyChange::ResetCircuitBreaker(asset))
+    }
+
+    /// Queue adding a liquidation venue to the allowlist (admin only).
+    /// Returns the proposal id.
+    pub fn queue_venue(env: Env, venue_hash: Bytes) -> u64 {
+        Self::require_admin(&env);
+        Self::queue_change(&env, PolicyChange::AddVenue(venue_hash))
+    }
+
+    fn require_listed(env: &Env, asset: &Symbol) {
+        if Self::get_policy(env.clone(), asset.clone()).is_none() {
+            panic!("policy not found");
+        }
+    }
+
+    fn queue_change(env: &Env, change: PolicyChange) -> u64 {
+        let proposal_id: u64 = env.storage().instance().get(&DataKey::NextProposalId).unwrap_or(1u64);
+        env.storage().instance().set(&DataKey::NextProposalId, &(proposal_id + 1));
+        
+        let eta = env.ledger().timestamp() + Self::get_policy_delay(env.clone());
+        let mut queued = Self::queued_policies(env);
+        queued.set(proposal_id, QueuedPolicy { change: change.clone(), eta });
+        env.storage().instance().set(&DataKey::QueuedPolicies, &queued);
+        
+        events::publish(env, "PolicyQueued", proposal_id, PolicyQueuedEvent { change, eta });
+        proposal_id
+    }
+
+    /// Apply a queued change once its delay has passed. Anyone may call this.
+    pub fn execute_policy(env: Env, proposal_id: u64) {
+        let mut queued = Self::queued_policies(&env);
+        let proposal = queued.get(proposal_id).expect("proposal not found");
+        if env.ledger().timestamp() < proposal.eta {
+            panic!("timelock not elapsed");
+        }
+        
+        queued.remove(proposal_id);
+        env.storage().instance().set(&DataKey::QueuedPolicies, &queued);
+        
+        match proposal.change {
+            PolicyChange::Policy(asset, policy) => Self::store_policy(&env, asset, policy),
+            PolicyChange::DisputeWindow(asset, seconds) => Self::store_dispute_window(&env, asset, seconds),
+            PolicyChange::LiquidationPenalty(asset, penalty) => Self::store_liquidation_penalty(&env, asset, penalty),
+            PolicyChange::ResetCircuitBreaker(asset) => Self::set_circuit_breaker(&env, asset, false),
+            PolicyChange::AddVenue(venue_hash) => Self::store_venue(&env, venue_hash),
+        }
+    }
+
+    /// Drop a queued policy before it executes (guardian or admin)
+    pub fn cancel_policy(env: Env, caller: Address, proposal_id: u64) {
+        caller.require_auth();
+        
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).expect("admin not set");
+        if caller != admin && Self::get_guardian(env.clone()) != Some(caller) {
+            panic!("not authorized");
+        }
+        
+        let mut queued = Self::queued_policies(&env);
+        let proposal = queued.get(proposal_id).expect("proposal not found");
+        queued.remove(proposal_id);
+        env.storage().instance().set(&DataKey::QueuedPolicies, &queued);
+        
+        events::publish(&env, "PolicyCancelled", proposal_id, PolicyCancelledEvent { change: proposal.change });
+    }
+
+    pub fn get_queued_policy(env: Env, proposal_id: u64) -> Option<QueuedPolicy> {
+        Self::queued_policies(&env).get(proposal_id)
+    }
+
+    /// Trip an asset's circuit breaker immediately (guardian only). Clearing
+    /// it goes through `queue_circuit_breaker_reset`.
+    pub fn emergency_halt(env: Env, asset: Symbol) {
+        let guardian = Self::get_guardian(env.clone()).expect("guardian not set");
+        guardian.require_auth();
+        
+        Self::set_circuit_breaker(&env, asset, true);
+    }
+
+    fn queued_policies(env: &Env) -> Map<u64, QueuedPolicy> {
+        env.storage()
+            .instance()
+            .get(&DataKey::QueuedPolicies)
+            .unwrap_or(Map::new(env))
+    }
+}
@@ -1,11 +1,18 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, String, Vec};
+
+mod events;
+
+#[cfg(test)]
+mod test;
+
+pub use events::*;
 
 // Price Oracle Adapter for Legasi protocol
 // Self-contained oracle that accepts price updates from authorized backend
 
 #[contracttype]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PriceData {
     pub asset_pair: String, // "BTC/USD", "USDC/USD"
     pub price: i128,        // Price in 8 decimal places (e.g., 100000000000 = $1000.00)
@@ -23,6 +30,20 @@ pub enum DataKey {
     StalenessLimit,
 }
 
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotAuthorized = 2,
+    InvalidPrice = 3,
+    InvalidConfidence = 4,
+    PriceNotFound = 5,
+    PriceStale = 6,
+    NoPriceHistory = 7,
+    NoRecentPrices = 8,
+}
+
 #[contract]
 pub struct PriceOracleContract;
 
@@ -33,11 +54,11 @@ impl PriceOracleContract {
         env: Env,
         admin: Address,
         staleness_limit: u64, // seconds
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         admin.require_auth();
 
         if env.storage().instance().has(&DataKey::StalenessLimit) {
-            return Err(String::from_str(&env, "Already initialized"));
+            return Err(Error::AlreadyInitialized);
         }
 
         env.storage()
@@ -51,21 +72,28 @@ impl PriceOracleContract {
     }
 
     /// Add authorized price publisher
-    pub fn add_publisher(env: Env, admin: Address, publisher: Address) -> Result<(), String> {
+    pub fn add_publisher(env: Env, admin: Address, publisher: Address) -> Result<(), Error> {
         admin.require_auth();
 
         // Verify admin is authorized
         if !env
             .storage()
             .instance()
-            .has(&DataKey::AuthorizedPublisher(admin))
+            .has(&DataKey::AuthorizedPublisher(admin.clone()))
         {
-            return Err(String::from_str(&env, "Not authorized"));
+            return Err(Error::NotAuthorized);
         }
 
         env.storage()
             .instance()
-            .set(&DataKey::AuthorizedPublisher(publisher), &true);
+            .set(&DataKey::AuthorizedPublisher(publisher.clone()), &true);
+
+        events::publish(
+            &env,
+            "PublisherAdded",
+            publisher,
+            PublisherAddedEvent { added_by: admin },
+        );
 
         Ok(())
     }
@@ -78,7 +106,7 @@ impl PriceOracleContract {
         price: i128,
         confidence: u32,
         source: String,
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         publisher.require_auth();
 
         // Verify publisher is authorized
@@ -87,17 +115,17 @@ impl PriceOracleContract {
             .instance()
             .has(&DataKey::AuthorizedPublisher(publisher))
         {
-            return Err(String::from_str(&env, "Not authorized"));
+            return Err(Error::NotAuthorized);
         }
 
         // Validate price
         if price <= 0 {
-            return Err(String::from_str(&env, "Invalid price"));
+            return Err(Error::InvalidPrice);
         }
 
         // Validate confidence
         if confidence > 10000 {
-            return Err(String::from_str(&env, "Invalid confidence"));
+            return Err(Error::InvalidConfidence);
         }
 
         // Create price data
@@ -106,7 +134,7 @@ impl PriceOracleContract {
             price,
             confidence,
             timestamp: env.ledger().timestamp(),
-            source,
+            source: source.clone(),
         };
 
         // Store current price
@@ -121,7 +149,7 @@ impl PriceOracleContract {
             .get(&DataKey::PriceHistory(asset_pair.clone()))
             .unwrap_or(Vec::new(&env));
 
-        history.push_back(price_data);
+        history.push_back(price_data.clone());
 
         // Keep only last 100 entries
         while history.len() > 100 {
@@ -130,18 +158,30 @@ impl PriceOracleContract {
 
         env.storage()
             .persistent()
-            .set(&DataKey::PriceHistory(asset_pair), &history);
+            .set(&DataKey::PriceHistory(asset_pair.clone()), &history);
+
+        events::publish(
+            &env,
+            "OracleUpdated",
+            asset_pair,
+            OracleUpdatedEvent {
+                price,
+                confidence,
+                timestamp: price_data.timestamp,
+                source,
+            },
+        );
 
         Ok(())
     }
 
     /// Get current price
-    pub fn get_price(env: Env, asset_pair: String) -> Result<PriceData, String> {
+    pub fn get_price(env: Env, asset_pair: String) -> Result<PriceData, Error> {
         let price_data: PriceData = env
             .storage()
             .persistent()
             .get(&DataKey::Price(asset_pair.clone()))
-            .ok_or(String::from_str(&env, "Price not found"))?;
+            .ok_or(Error::PriceNotFound)?;
 
         // Check staleness
         let staleness_limit: u64 = env
@@ -152,7 +192,7 @@ impl PriceOracleContract {
 
         let age = env.ledger().timestamp() - price_data.timestamp;
         if age > staleness_limit {
-            return Err(String::from_str(&env, "Price stale"));
+            return Err(Error::PriceStale);
         }
 
         Ok(price_data)
@@ -167,11 +207,11 @@ impl PriceOracleContract {
     }
 
     /// Calculate TWAP (Time-Weighted Average Price)
-    pub fn get_twap(env: Env, asset_pair: String, duration: u64) -> Result<i128, String> {
+    pub fn get_twap(env: Env, asset_pair: String, duration: u64) -> Result<i128, Error> {
         let history: Vec<PriceData> = Self::get_price_history(env.clone(), asset_pair);
 
         if history.is_empty() {
-            return Err(String::from_str(&env, "No price history"));
+            return Err(Error::NoPriceHistory);
         }
 
         let cutoff_time = env.ledger().timestamp() - duration;
@@ -187,7 +227,7 @@ impl PriceOracleContract {
         }
 
         if count == 0 {
-            return Err(String::from_str(&env, "No recent prices"));
+            return Err(Error::NoRecentPrices);
         }
 
         Ok(total_price / count as i128)
@@ -0,0 +1,100 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{vec, IntoVal, Symbol, Val};
+
+struct Setup {
+    env: Env,
+    oracle: PriceOracleContractClient<'static>,
+    admin: Address,
+    pair: String,
+}
+
+/// Oracle with the admin as its only publisher and a 60 second staleness limit
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let oracle = PriceOracleContractClient::new(&env, &env.register_contract(None, PriceOracleContract));
+    let admin = Address::generate(&env);
+    oracle.initialize(&admin, &60);
+
+    let pair = String::from_str(&env, "BTC/USD");
+    Setup { env, oracle, admin, pair }
+}
+
+/// The event `name` as the oracle publishes it
+fn event<I, D>(s: &Setup, name: &str, id: I, data: D) -> (Address, soroban_sdk::Vec<Val>, Val)
+where
+    I: IntoVal<Env, Val>,
+    D: IntoVal<Env, Val>,
+{
+    (
+        s.oracle.address.clone(),
+        (Symbol::new(&s.env, "price_oracle_contract"), Symbol::new(&s.env, name), EVENT_VERSION, id)
+            .into_val(&s.env),
+        data.into_val(&s.env),
+    )
+}
+
+#[test]
+fn add_publisher_emits_publisher_added() {
+    let s = setup();
+    let publisher = Address::generate(&s.env);
+    s.oracle.add_publisher(&s.admin, &publisher);
+
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "PublisherAdded", publisher, PublisherAddedEvent { added_by: s.admin.clone() })
+        ]
+    );
+}
+
+#[test]
+fn publish_price_emits_oracle_updated() {
+    let s = setup();
+    let source = String::from_str(&s.env, "aggregated");
+    s.oracle.publish_price(&s.admin, &s.pair, &6_000_000_000_000, &9_500, &source);
+
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            event(&s, "OracleUpdated", s.pair.clone(), OracleUpdatedEvent {
+                price: 6_000_000_000_000,
+                confidence: 9_500,
+                timestamp: 1_000,
+                source,
+            })
+        ]
+    );
+}
+
+#[test]
+fn unauthorized_publisher_is_rejected() {
+    let s = setup();
+    let stranger = Address::generate(&s.env);
+    let source = String::from_str(&s.env, "aggregated");
+
+    assert_eq!(
+        s.oracle.try_publish_price(&stranger, &s.pair, &1, &9_500, &source),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(s.oracle.try_add_publisher(&stranger, &stranger), Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn get_price_reports_missing_and_stale_prices() {
+    let s = setup();
+    assert_eq!(s.oracle.try_get_price(&s.pair), Err(Ok(Error::PriceNotFound)));
+
+    s.oracle.publish_price(&s.admin, &s.pair, &100, &9_500, &String::from_str(&s.env, "bybit"));
+    assert_eq!(s.oracle.get_price(&s.pair).price, 100);
+
+    s.env.ledger().set_timestamp(1_061);
+    assert_eq!(s.oracle.try_get_price(&s.pair), Err(Ok(Error::PriceStale)));
+}
@@ -1,6 +1,13 @@
 #![no_std]
 use soroban_sdk::{contract, contractimpl, contracttype, Env, Address, Symbol, Map};
 
+mod events;
+
+#[cfg(test)]
+mod test;
+
+pub use events::*;
+
 #[contracttype]
 #[derive(Clone)]
 pub struct PriceRound {
@@ -16,6 +23,7 @@ pub enum DataKey {
     Admin,
     Prices,
     MaxJumpBps,
+    Round(Symbol, i128),
 }
 
 #[contract]
@@ -54,6 +62,10 @@ impl PriceAdapter {
             .unwrap_or(Map::new(&env));
         
         if let Some(prev) = prices.get(asset.clone()) {
+            // Rounds are looked up by id, so an id may never be reused or go back
+            if round_id <= prev.round_id {
+                panic!("stale round");
+            }
             if prev.price > 0 {
                 let max_jump: i128 = env.storage()
                     .instance()
@@ -77,15 +89,18 @@ impl PriceAdapter {
             price,
             timestamp,
             round_id,
-            source,
+            source: source.clone(),
         };
         
-        prices.set(asset.clone(), round);
+        prices.set(asset.clone(), round.clone());
         env.storage().instance().set(&DataKey::Prices, &prices);
+        env.storage().persistent().set(&DataKey::Round(asset.clone(), round_id), &round);
 
-        env.events().publish(
-            (Symbol::new(&env, "OracleUpdated"), asset),
-            (price, timestamp, round_id)
+        events::publish(
+            &env,
+            "OracleUpdated",
+            asset,
+            OracleUpdatedEvent { price, timestamp, round_id, source },
         );
     }
 
@@ -97,6 +112,12 @@ impl PriceAdapter {
         prices.get(asset)
     }
 
+    /// Look up a past round of an asset by id, e.g. to check the round a
+    /// liquidation was executed against
+    pub fn get_round(env: Env, asset: Symbol, round_id: i128) -> Option<PriceRound> {
+        env.storage().persistent().get(&DataKey::Round(asset, round_id))
+    }
+
     pub fn is_fresh(env: Env, asset: Symbol, max_age_seconds: u64) -> bool {
         if let Some(round) = Self::get_price(env.clone(), asset) {
             let now = env.ledger().timestamp();
@@ -0,0 +1,66 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, Events, Ledger};
+use soroban_sdk::{vec, IntoVal};
+
+#[test]
+fn update_price_emits_oracle_updated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000_000);
+
+    let adapter = PriceAdapterClient::new(&env, &env.register(PriceAdapter, ()));
+    adapter.initialize(&Address::generate(&env), &1_000);
+
+    let asset = Symbol::new(&env, "BTC");
+    let source = Symbol::new(&env, "feed");
+    adapter.update_price(&asset, &600_000_000_000, &7, &source);
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                adapter.address.clone(),
+                (Symbol::new(&env, "price_adapter"), Symbol::new(&env, "OracleUpdated"), EVENT_VERSION, asset).into_val(&env),
+                OracleUpdatedEvent { price: 600_000_000_000, timestamp: 1_000_000, round_id: 7, source }.into_val(&env),
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "stale round")]
+fn update_price_rejects_a_reused_round() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let adapter = PriceAdapterClient::new(&env, &env.register(PriceAdapter, ()));
+    adapter.initialize(&Address::generate(&env), &1_000);
+
+    let asset = Symbol::new(&env, "BTC");
+    let source = Symbol::new(&env, "feed");
+    adapter.update_price(&asset, &600_000_000_000, &7, &source);
+    adapter.update_price(&asset, &610_000_000_000, &7, &source);
+}
+
+#[test]
+fn update_price_keeps_earlier_rounds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let adapter = PriceAdapterClient::new(&env, &env.register(PriceAdapter, ()));
+    adapter.initialize(&Address::generate(&env), &1_000);
+
+    let asset = Symbol::new(&env, "BTC");
+    let source = Symbol::new(&env, "feed");
+    adapter.update_price(&asset, &600_000_000_000, &7, &source);
+    adapter.update_price(&asset, &610_000_000_000, &8, &source);
+
+    // An older round cannot overwrite the one a receipt was checked against
+    assert!(adapter.try_update_price(&asset, &600_000_000_000, &6, &source).is_err());
+    assert_eq!(adapter.get_round(&asset, &7).unwrap().price, 600_000_000_000);
+    assert_eq!(adapter.get_price(&asset).unwrap().round_id, 8);
+}
@@ -6,7 +6,7 @@ echo "Building Soroban contracts..."
 # Build all contracts
 cd contracts
 
-for dir in loans policy_registry price_adapter liquidation_manager; do
+for dir in loans policy_registry price_adapter liquidation_manager insurance_fund; do
   echo "Building $dir..."
   cd $dir
   stellar contract build